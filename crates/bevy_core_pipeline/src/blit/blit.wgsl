@@ -3,7 +3,41 @@
 @group(0) @binding(0) var in_texture: texture_2d<f32>;
 @group(0) @binding(1) var in_sampler: sampler;
 
+fn encode_srgb(linear: vec3<f32>) -> vec3<f32> {
+    let low = linear * 12.92;
+    let high = 1.055 * pow(linear, vec3(1.0 / 2.4)) - 0.055;
+    return select(high, low, linear <= vec3(0.0031308));
+}
+
+fn encode_bt709(linear: vec3<f32>) -> vec3<f32> {
+    let low = linear * 4.5;
+    let high = 1.099 * pow(linear, vec3(0.45)) - 0.099;
+    return select(high, low, linear < vec3(0.018));
+}
+
+fn decode_srgb(encoded: vec3<f32>) -> vec3<f32> {
+    let low = encoded / 12.92;
+    let high = pow((encoded + 0.055) / 1.055, vec3(2.4));
+    return select(high, low, encoded <= vec3(0.04045));
+}
+
 @fragment
 fn fs_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(in_texture, in_sampler, in.uv);
+    let color = textureSample(in_texture, in_sampler, in.uv);
+#ifdef OUTPUT_ENCODING
+    // `pow` returns NaN for negative values.
+    var rgb = max(color.rgb, vec3(0.0));
+#ifdef ENCODE_SRGB
+    rgb = encode_srgb(rgb);
+#endif
+#ifdef ENCODE_BT709
+    rgb = encode_bt709(rgb);
+#endif
+#ifdef DECODE_SRGB
+    rgb = decode_srgb(rgb);
+#endif
+    return vec4(rgb, color.a);
+#else
+    return color;
+#endif
 }
@@ -2,6 +2,7 @@ use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, Handle};
 use bevy_ecs::prelude::*;
 use bevy_render::{
+    camera::OutputColorSpace,
     render_resource::{
         binding_types::{sampler, texture_2d},
         *,
@@ -72,19 +73,43 @@ pub struct BlitPipelineKey {
     pub texture_format: TextureFormat,
     pub blend_state: Option<BlendState>,
     pub samples: u32,
+    /// If `Some`, colors are written with this encoding regardless of `texture_format`.
+    /// Otherwise they're written as is.
+    pub output_color_space: Option<OutputColorSpace>,
 }
 
 impl SpecializedRenderPipeline for BlitPipeline {
     type Key = BlitPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        let srgb_target = key.texture_format.is_srgb();
+        match key.output_color_space {
+            // The GPU already encodes to sRGB when writing to sRGB formats.
+            Some(OutputColorSpace::Srgb) if !srgb_target => shader_defs.push("ENCODE_SRGB".into()),
+            Some(OutputColorSpace::Bt709) => shader_defs.push("ENCODE_BT709".into()),
+            _ => {}
+        }
+        if srgb_target
+            && matches!(
+                key.output_color_space,
+                Some(OutputColorSpace::Linear | OutputColorSpace::Bt709)
+            )
+        {
+            // Cancel out the sRGB encoding the GPU applies when writing to sRGB formats.
+            shader_defs.push("DECODE_SRGB".into());
+        }
+        if !shader_defs.is_empty() {
+            shader_defs.push("OUTPUT_ENCODING".into());
+        }
+
         RenderPipelineDescriptor {
             label: Some("blit pipeline".into()),
             layout: vec![self.texture_bind_group.clone()],
             vertex: fullscreen_shader_vertex_state(),
             fragment: Some(FragmentState {
                 shader: BLIT_SHADER_HANDLE,
-                shader_defs: vec![],
+                shader_defs,
                 entry_point: "fs_main".into(),
                 targets: vec![Some(ColorTargetState {
                     format: key.texture_format,
@@ -73,6 +73,9 @@ impl ViewNode for MainOpaquePass3dNode {
             if let Some(viewport) = camera.viewport.as_ref() {
                 render_pass.set_camera_viewport(viewport);
             }
+            if let Some(scissor) = camera.scissor {
+                render_pass.set_camera_scissor(scissor);
+            }
 
             // Opaque draws
             if !opaque_phase.items.is_empty() {
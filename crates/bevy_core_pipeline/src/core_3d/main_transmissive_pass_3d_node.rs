@@ -88,6 +88,9 @@ impl ViewNode for MainTransmissivePass3dNode {
                     if let Some(viewport) = camera.viewport.as_ref() {
                         render_pass.set_camera_viewport(viewport);
                     }
+                    if let Some(scissor) = camera.scissor {
+                        render_pass.set_camera_scissor(scissor);
+                    }
 
                     // render items in range
                     transmissive_phase.render_range(&mut render_pass, world, view_entity, range);
@@ -99,6 +102,9 @@ impl ViewNode for MainTransmissivePass3dNode {
                 if let Some(viewport) = camera.viewport.as_ref() {
                     render_pass.set_camera_viewport(viewport);
                 }
+                if let Some(scissor) = camera.scissor {
+                    render_pass.set_camera_scissor(scissor);
+                }
 
                 transmissive_phase.render(&mut render_pass, world, view_entity);
             }
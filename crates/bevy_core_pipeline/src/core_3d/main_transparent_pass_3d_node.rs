@@ -54,6 +54,9 @@ impl ViewNode for MainTransparentPass3dNode {
             if let Some(viewport) = camera.viewport.as_ref() {
                 render_pass.set_camera_viewport(viewport);
             }
+            if let Some(scissor) = camera.scissor {
+                render_pass.set_camera_scissor(scissor);
+            }
 
             transparent_phase.render(&mut render_pass, world, view_entity);
         }
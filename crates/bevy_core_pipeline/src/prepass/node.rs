@@ -87,6 +87,9 @@ impl ViewNode for PrepassNode {
             if let Some(viewport) = camera.viewport.as_ref() {
                 render_pass.set_camera_viewport(viewport);
             }
+            if let Some(scissor) = camera.scissor {
+                render_pass.set_camera_scissor(scissor);
+            }
 
             // Opaque draws
             if !opaque_prepass_phase.items.is_empty() {
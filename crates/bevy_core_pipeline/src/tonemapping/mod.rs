@@ -2,8 +2,8 @@ use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, Assets, Handle};
 use bevy_ecs::prelude::*;
-use bevy_reflect::Reflect;
-use bevy_render::camera::Camera;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::camera::{Camera, OutputColorSpace};
 use bevy_render::extract_component::{ExtractComponent, ExtractComponentPlugin};
 use bevy_render::extract_resource::{ExtractResource, ExtractResourcePlugin};
 use bevy_render::render_asset::{RenderAssetUsages, RenderAssets};
@@ -84,10 +84,14 @@ impl Plugin for TonemappingPlugin {
             app.insert_resource(tonemapping_luts);
         }
 
-        app.add_plugins(ExtractResourcePlugin::<TonemappingLuts>::default());
+        app.init_resource::<ColorPipelineDebug>().add_plugins((
+            ExtractResourcePlugin::<TonemappingLuts>::default(),
+            ExtractResourcePlugin::<ColorPipelineDebug>::default(),
+        ));
 
         app.register_type::<Tonemapping>();
         app.register_type::<DebandDither>();
+        app.register_type::<ColorPipelineDebug>();
 
         app.add_plugins((
             ExtractComponentPlugin::<Tonemapping>::default(),
@@ -175,10 +179,53 @@ impl Tonemapping {
     }
 }
 
+/// Global switches that skip parts of the color pipeline, to inspect the linear colors a scene
+/// renders before they are prepared for display.
+///
+/// These only affect cameras with [`Camera::hdr`] enabled, since other cameras apply
+/// tonemapping in their material shaders instead of in a separate pass.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect, ExtractResource)]
+#[reflect(Resource, Default)]
+pub struct ColorPipelineDebug {
+    /// Renders every camera as if it used [`Tonemapping::None`].
+    pub bypass_tonemapping: bool,
+    /// Cancels out the sRGB encoding applied when writing to the output texture, so that the
+    /// displayed values are the linear values the scene rendered.
+    ///
+    /// This only affects cameras whose output texture has an sRGB format and that don't override
+    /// the encoding with an [`OutputColorSpace`] other than [`OutputColorSpace::Srgb`].
+    pub bypass_gamma: bool,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct TonemappingPipelineKey {
     deband_dither: DebandDither,
     tonemapping: Tonemapping,
+    bypass_gamma: bool,
+}
+
+impl TonemappingPipelineKey {
+    fn new(
+        tonemapping: Option<&Tonemapping>,
+        dither: Option<&DebandDither>,
+        color_pipeline_debug: &ColorPipelineDebug,
+        out_texture_format: TextureFormat,
+        output_color_space: Option<&OutputColorSpace>,
+    ) -> Self {
+        let tonemapping = match tonemapping {
+            Some(tonemapping) if !color_pipeline_debug.bypass_tonemapping => *tonemapping,
+            _ => Tonemapping::None,
+        };
+        // Only the sRGB encoding the GPU applies when writing to the output texture is canceled
+        // out.
+        let srgb_output = out_texture_format.is_srgb()
+            && matches!(output_color_space, None | Some(OutputColorSpace::Srgb));
+        TonemappingPipelineKey {
+            deband_dither: *dither.unwrap_or(&DebandDither::Disabled),
+            tonemapping,
+            bypass_gamma: color_pipeline_debug.bypass_gamma && srgb_output,
+        }
+    }
 }
 
 impl SpecializedRenderPipeline for TonemappingPipeline {
@@ -189,6 +236,9 @@ impl SpecializedRenderPipeline for TonemappingPipeline {
         if let DebandDither::Enabled = key.deband_dither {
             shader_defs.push("DEBAND_DITHER".into());
         }
+        if key.bypass_gamma {
+            shader_defs.push("BYPASS_GAMMA".into());
+        }
 
         match key.tonemapping {
             Tonemapping::None => shader_defs.push("TONEMAP_METHOD_NONE".into()),
@@ -288,13 +338,23 @@ pub fn prepare_view_tonemapping_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<TonemappingPipeline>>,
     upscaling_pipeline: Res<TonemappingPipeline>,
-    view_targets: Query<(Entity, Option<&Tonemapping>, Option<&DebandDither>), With<ViewTarget>>,
+    color_pipeline_debug: Res<ColorPipelineDebug>,
+    view_targets: Query<(
+        Entity,
+        &ViewTarget,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+        Option<&OutputColorSpace>,
+    )>,
 ) {
-    for (entity, tonemapping, dither) in view_targets.iter() {
-        let key = TonemappingPipelineKey {
-            deband_dither: *dither.unwrap_or(&DebandDither::Disabled),
-            tonemapping: *tonemapping.unwrap_or(&Tonemapping::None),
-        };
+    for (entity, view_target, tonemapping, dither, output_color_space) in view_targets.iter() {
+        let key = TonemappingPipelineKey::new(
+            tonemapping,
+            dither,
+            &color_pipeline_debug,
+            view_target.out_texture_format(),
+            output_color_space,
+        );
         let pipeline = pipelines.specialize(&pipeline_cache, &upscaling_pipeline, key);
 
         commands
@@ -391,3 +451,45 @@ pub fn lut_placeholder() -> Image {
         asset_usage: RenderAssetUsages::RENDER_WORLD,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorPipelineDebug, DebandDither, Tonemapping, TonemappingPipelineKey};
+    use bevy_render::{camera::OutputColorSpace, render_resource::TextureFormat};
+
+    #[test]
+    fn color_pipeline_debug_bypasses_tonemapping_and_gamma() {
+        let key = |debug: &ColorPipelineDebug, format, output_color_space| {
+            TonemappingPipelineKey::new(
+                Some(&Tonemapping::TonyMcMapface),
+                Some(&DebandDither::Enabled),
+                debug,
+                format,
+                output_color_space,
+            )
+        };
+        let srgb = TextureFormat::Bgra8UnormSrgb;
+
+        let mut debug = ColorPipelineDebug::default();
+        let default_key = key(&debug, srgb, None);
+        assert_eq!(default_key.tonemapping, Tonemapping::TonyMcMapface);
+        assert!(!default_key.bypass_gamma);
+
+        debug.bypass_tonemapping = true;
+        let bypassed = key(&debug, srgb, None);
+        assert_eq!(bypassed.tonemapping, Tonemapping::None);
+        assert_eq!(bypassed.deband_dither, DebandDither::Enabled);
+        assert!(!bypassed.bypass_gamma);
+
+        debug.bypass_gamma = true;
+        assert!(key(&debug, srgb, None).bypass_gamma);
+        assert!(key(&debug, srgb, Some(&OutputColorSpace::Srgb)).bypass_gamma);
+
+        // There is no sRGB encoding to cancel out otherwise.
+        assert!(!key(&debug, TextureFormat::Bgra8Unorm, None).bypass_gamma);
+        assert!(!key(&debug, srgb, Some(&OutputColorSpace::Linear)).bypass_gamma);
+
+        debug = ColorPipelineDebug::default();
+        assert_eq!(key(&debug, srgb, None), default_key);
+    }
+}
@@ -13,6 +13,15 @@
 @group(0) @binding(3) var dt_lut_texture: texture_3d<f32>;
 @group(0) @binding(4) var dt_lut_sampler: sampler;
 
+#ifdef BYPASS_GAMMA
+// The inverse of the sRGB encoding the GPU applies when writing to sRGB textures.
+fn decode_srgb(encoded: vec3<f32>) -> vec3<f32> {
+    let low = encoded / 12.92;
+    let high = pow((encoded + 0.055) / 1.055, vec3(2.4));
+    return select(high, low, encoded <= vec3(0.04045));
+}
+#endif
+
 @fragment
 fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     let hdr_color = textureSample(hdr_texture, hdr_sampler, in.uv);
@@ -27,5 +36,11 @@ fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     output_rgb = powsafe(output_rgb.rgb, 2.2);
 #endif
 
+#ifdef BYPASS_GAMMA
+    // Undo the SRGB conversion the GPU applies to our output, so the linear values are displayed
+    // as they are. `pow` returns NaN for negative values.
+    output_rgb = decode_srgb(max(output_rgb, vec3(0.0)));
+#endif
+
     return vec4<f32>(output_rgb, hdr_color.a);
 }
@@ -1,7 +1,7 @@
 use crate::blit::{BlitPipeline, BlitPipelineKey};
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
-use bevy_render::camera::{CameraOutputMode, ExtractedCamera};
+use bevy_render::camera::{CameraOutputMode, ExtractedCamera, OutputColorSpace};
 use bevy_render::view::ViewTarget;
 use bevy_render::{render_resource::*, Render, RenderApp, RenderSet};
 
@@ -30,9 +30,14 @@ fn prepare_view_upscaling_pipelines(
     mut pipeline_cache: ResMut<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
     blit_pipeline: Res<BlitPipeline>,
-    view_targets: Query<(Entity, &ViewTarget, Option<&ExtractedCamera>)>,
+    view_targets: Query<(
+        Entity,
+        &ViewTarget,
+        Option<&ExtractedCamera>,
+        Option<&OutputColorSpace>,
+    )>,
 ) {
-    for (entity, view_target, camera) in view_targets.iter() {
+    for (entity, view_target, camera, output_color_space) in view_targets.iter() {
         let blend_state = if let Some(ExtractedCamera {
             output_mode: CameraOutputMode::Write { blend_state, .. },
             ..
@@ -46,6 +51,7 @@ fn prepare_view_upscaling_pipelines(
             texture_format: view_target.out_texture_format(),
             blend_state,
             samples: 1,
+            output_color_space: output_color_space.copied(),
         };
         let pipeline = pipelines.specialize(&pipeline_cache, &blit_pipeline, key);
 
@@ -161,6 +161,10 @@ impl Touch {
     }
 
     /// Returns the `id` of the touch.
+    ///
+    /// The id stays the same from the [`TouchPhase::Started`] event of a finger until its
+    /// [`TouchPhase::Ended`] or [`TouchPhase::Canceled`] event, but may be given to another finger
+    /// afterwards.
     #[inline]
     pub fn id(&self) -> u64 {
         self.id
@@ -227,6 +231,10 @@ impl From<&TouchInput> for Touch {
 /// ## Updating
 ///
 /// The resource is updated inside of the [`touch_screen_input_system`].
+///
+/// Touches are keyed by the id of their finger, which the platform may reuse once the finger is
+/// lifted. If a finger starts with the id of a touch that is still pressed, because the platform
+/// never reported the end of the previous touch, the previous touch is treated as canceled.
 #[derive(Debug, Clone, Default, Resource)]
 pub struct Touches {
     /// A collection of every [`Touch`] that is currently being pressed.
@@ -368,7 +376,9 @@ impl Touches {
     fn process_touch_event(&mut self, event: &TouchInput) {
         match event.phase {
             TouchPhase::Started => {
-                self.pressed.insert(event.id, event.into());
+                if let Some(stale) = self.pressed.insert(event.id, event.into()) {
+                    self.just_canceled.insert(event.id, stale);
+                }
                 self.just_pressed.insert(event.id, event.into());
             }
             TouchPhase::Moved => {
@@ -550,6 +560,58 @@ mod test {
         assert_ne!(touch.previous_position, touch.position);
     }
 
+    #[test]
+    fn touch_ids_follow_fingers() {
+        use crate::{touch::TouchPhase, TouchInput, Touches};
+        use bevy_ecs::entity::Entity;
+        use bevy_math::Vec2;
+
+        let touch = |phase, id, x| TouchInput {
+            phase,
+            position: Vec2::new(x, 0.0),
+            window: Entity::PLACEHOLDER,
+            force: None,
+            id,
+        };
+        let mut touches = Touches::default();
+
+        // Two fingers down, then the first one lifts while the second one moves.
+        touches.process_touch_event(&touch(TouchPhase::Started, 0, 1.0));
+        touches.process_touch_event(&touch(TouchPhase::Started, 1, 2.0));
+        touches.update();
+        touches.process_touch_event(&touch(TouchPhase::Ended, 0, 1.0));
+        touches.process_touch_event(&touch(TouchPhase::Moved, 1, 3.0));
+        assert!(touches.just_released(0));
+        assert_eq!(
+            touches.get_pressed(1).unwrap().start_position(),
+            Vec2::new(2.0, 0.0)
+        );
+        assert_eq!(
+            touches.get_pressed(1).unwrap().position(),
+            Vec2::new(3.0, 0.0)
+        );
+
+        // A new finger reuses the id of the lifted one, and starts a new touch.
+        touches.update();
+        touches.process_touch_event(&touch(TouchPhase::Started, 0, 5.0));
+        assert!(touches.just_pressed(0));
+        assert_eq!(
+            touches.get_pressed(0).unwrap().start_position(),
+            Vec2::new(5.0, 0.0)
+        );
+        assert_eq!(touches.iter().count(), 2);
+
+        // Starting a touch that never ended cancels it instead of silently replacing it.
+        touches.update();
+        touches.process_touch_event(&touch(TouchPhase::Started, 1, 7.0));
+        assert!(touches.just_canceled(1));
+        assert!(touches.just_pressed(1));
+        assert_eq!(
+            touches.get_pressed(1).unwrap().start_position(),
+            Vec2::new(7.0, 0.0)
+        );
+    }
+
     #[test]
     fn touch_pressed() {
         use crate::{touch::TouchPhase, TouchInput, Touches};
@@ -32,7 +32,7 @@ use bevy_render::{
         BevyDefault, DefaultImageSampler, GpuImage, Image, ImageSampler, TextureFormatPixelInfo,
     },
     view::{ViewTarget, ViewUniformOffset, ViewVisibility},
-    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    Extract, ExtractSchedule, ExtractSet, Render, RenderApp, RenderSet,
 };
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::{tracing::error, Entry, HashMap, Hashed};
@@ -131,7 +131,7 @@ impl Plugin for MeshRenderPlugin {
                 .allow_ambiguous_resource::<GpuArrayBuffer<MeshUniform>>()
                 .add_systems(
                     ExtractSchedule,
-                    (extract_meshes, extract_skins, extract_morphs),
+                    (extract_meshes, extract_skins, extract_morphs).in_set(ExtractSet::Meshes),
                 )
                 .add_systems(
                     Render,
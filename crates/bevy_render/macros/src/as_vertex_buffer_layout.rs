@@ -0,0 +1,85 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenTree;
+use quote::quote;
+use syn::{Data, DataStruct, DeriveInput, Error, Fields, LitInt, Path, Result};
+
+const LOCATION_ATTRIBUTE_NAME: &str = "location";
+
+pub fn derive_as_vertex_buffer_layout(ast: DeriveInput) -> Result<TokenStream> {
+    let bevy_render_path: Path = crate::bevy_render_path();
+
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(Error::new_spanned(
+                &ast,
+                "Expected a struct with named fields",
+            ));
+        }
+    };
+
+    // Attribute offsets are computed by packing the fields in declaration order, which only
+    // matches the memory layout of `#[repr(C)]` structs.
+    let repr_c = ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+        .filter_map(|attr| attr.meta.require_list().ok())
+        .any(|list| {
+            list.tokens
+                .clone()
+                .into_iter()
+                .any(|token| matches!(token, TokenTree::Ident(ident) if ident == "C"))
+        });
+    if !repr_c {
+        return Err(Error::new_spanned(
+            &ast.ident,
+            "AsVertexBufferLayout can only be derived for `#[repr(C)]` structs",
+        ));
+    }
+    if !ast.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &ast.generics,
+            "AsVertexBufferLayout can't be derived for generic structs",
+        ));
+    }
+
+    let mut attributes = Vec::with_capacity(fields.len());
+    let mut attribute_sizes = Vec::with_capacity(fields.len());
+    let mut next_location = 0u32;
+    for field in fields {
+        let location = match field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident(LOCATION_ATTRIBUTE_NAME))
+        {
+            Some(attr) => attr.parse_args::<LitInt>()?.base10_parse::<u32>()?,
+            None => next_location,
+        };
+        next_location = location + 1;
+
+        let field_ty = &field.ty;
+        let format = quote! { <#field_ty as #bevy_render_path::render_resource::VertexAttributeType>::FORMAT };
+        attribute_sizes.push(quote! { #format.size() });
+        attributes.push(quote! { (#location, #format) });
+    }
+
+    let struct_name = &ast.ident;
+
+    Ok(TokenStream::from(quote! {
+        impl #bevy_render_path::render_resource::AsVertexBufferLayout for #struct_name {
+            const ATTRIBUTES: &'static [(u32, #bevy_render_path::render_resource::VertexFormat)] = &[
+                #(#attributes,)*
+            ];
+        }
+
+        // Padding between or after the fields would put the attributes at the wrong offsets.
+        const _: () = assert!(
+            0 #(+ #attribute_sizes)* == ::core::mem::size_of::<#struct_name>() as u64,
+            "the fields of an AsVertexBufferLayout struct must not be padded",
+        );
+    }))
+}
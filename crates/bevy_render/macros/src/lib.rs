@@ -2,6 +2,7 @@
 #![allow(missing_docs)]
 
 mod as_bind_group;
+mod as_vertex_buffer_layout;
 mod extract_component;
 mod extract_resource;
 
@@ -63,6 +64,21 @@ pub fn derive_as_bind_group(input: TokenStream) -> TokenStream {
     as_bind_group::derive_as_bind_group(input).unwrap_or_else(|err| err.to_compile_error().into())
 }
 
+/// Implements `AsVertexBufferLayout` for a vertex struct.
+///
+/// Each field becomes a vertex attribute whose format is taken from its `VertexAttributeType`
+/// impl. Fields are densely packed in declaration order, so the struct must be `#[repr(C)]` and
+/// must not contain padding, which is checked at compile time. Generic structs aren't supported.
+/// The shader location of a field can be set with `#[location(N)]`, and otherwise follows the
+/// location of the previous field (starting at zero).
+#[proc_macro_derive(AsVertexBufferLayout, attributes(location))]
+pub fn derive_as_vertex_buffer_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    as_vertex_buffer_layout::derive_as_vertex_buffer_layout(input)
+        .unwrap_or_else(|err| err.to_compile_error().into())
+}
+
 /// Derive macro generating an impl of the trait `RenderLabel`.
 ///
 /// This does not work for unions.
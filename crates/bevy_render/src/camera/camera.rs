@@ -65,6 +65,25 @@ impl Default for Viewport {
     }
 }
 
+/// Restricts the main passes of a [`Camera`] to a rectangle of its [`RenderTarget`], for example
+/// to redraw only the part of a UI that changed.
+///
+/// Fragments outside of the rectangle are discarded, but the image keeps the projection of the
+/// whole viewport. This doesn't restrict the clear: a pass that clears its attachments clears them
+/// entirely, so with [`ClearColorConfig::Default`], which uses the [`ClearColor`](super::ClearColor)
+/// resource, or [`ClearColorConfig::Custom`], the pixels outside the rectangle are cleared as usual.
+/// Use [`ClearColorConfig::None`] to keep their previous content. Post processing and the
+/// upscaling pass still cover the whole viewport.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default)]
+pub struct ScissorRect {
+    /// The physical position of the rectangle within the [`RenderTarget`] of this [`Camera`].
+    /// (0,0) corresponds to the top-left corner
+    pub physical_position: UVec2,
+    /// The physical size of the rectangle.
+    pub physical_size: UVec2,
+}
+
 /// Information about the current [`RenderTarget`].
 #[derive(Default, Debug, Clone)]
 pub struct RenderTargetInfo {
@@ -788,6 +807,84 @@ impl Default for CameraMainTextureUsages {
     }
 }
 
+/// Disables the automatic MSAA resolve of the main texture for this camera.
+///
+/// By default, passes rendering to [`ViewTarget::get_color_attachment`](crate::view::ViewTarget::get_color_attachment)
+/// resolve the multisampled texture into the main texture when they end. With this component,
+/// they only write to the multisampled texture, available from
+/// [`ViewTarget::sampled_main_texture_view`](crate::view::ViewTarget::sampled_main_texture_view)
+/// with [`TextureUsages::TEXTURE_BINDING`] so that it can be read in a shader. A custom pass is
+/// then responsible for resolving it into [`ViewTarget::main_texture_view`](crate::view::ViewTarget::main_texture_view)
+/// before post processing runs.
+///
+/// This has no effect when [`Msaa`](crate::view::Msaa) is [`Msaa::Off`](crate::view::Msaa::Off).
+/// Cameras rendering to the same target with different resolve settings don't share their main
+/// textures.
+#[derive(Component, ExtractComponent, Default, Clone, Copy, Reflect)]
+#[reflect(Component, Default)]
+pub struct ManualMsaaResolve;
+
+/// Renders a camera at a fraction of the resolution of its [`RenderTarget`].
+///
+/// The main textures of the camera, and every texture sized from
+/// [`ExtractedCamera::physical_target_size`] such as the depth texture, are allocated at the scaled
+/// resolution. The upscaling pass then stretches the main texture over the whole render target.
+/// Other cameras rendering to the same target, for example a UI camera, keep rendering at native
+/// resolution and don't share their main textures with this one.
+///
+/// A factor of `0.7` renders at 70% of the width and height of the target. Factors above `1.0`
+/// render at a higher resolution than the target.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct RenderScale(pub f32);
+
+impl Default for RenderScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl RenderScale {
+    /// Scales a physical size by this factor, rounding to the nearest pixel but no lower than one.
+    pub fn scale(&self, size: UVec2) -> UVec2 {
+        (size.as_vec2() * self.0).round().as_uvec2().max(UVec2::ONE)
+    }
+
+    /// Scales the physical `viewport` of a camera and the size of its render target, keeping the
+    /// scaled viewport inside the scaled target.
+    pub fn scale_viewport(&self, viewport: URect, target_size: UVec2) -> (URect, UVec2) {
+        let target_size = self.scale(target_size);
+        let min = (viewport.min.as_vec2() * self.0)
+            .floor()
+            .as_uvec2()
+            .min(target_size - UVec2::ONE);
+        let size = self.scale(viewport.size()).min(target_size - min);
+        (URect::from_corners(min, min + size), target_size)
+    }
+}
+
+/// Overrides the color encoding a camera writes to its [`RenderTarget`] with, for example when
+/// the target is an offscreen texture handed to a video encoder that expects BT.709.
+///
+/// By default, the final pass writes linear colors and relies on the format of the target for
+/// the encoding: `*Srgb` formats are sRGB encoded by the GPU, while other formats, like
+/// [`TextureFormat::Rgba16Float`], store linear values. With this component, the stored values
+/// use the requested encoding whatever the format of the target is. Only the transfer function
+/// changes: colors keep the sRGB/BT.709 primaries, which both spaces share.
+///
+/// The encoding is applied by the upscaling pass, so it has no effect with
+/// [`CameraOutputMode::Skip`].
+#[derive(Component, ExtractComponent, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, Debug, PartialEq)]
+pub enum OutputColorSpace {
+    /// Linear values, without a transfer function.
+    Linear,
+    /// The sRGB transfer function, used by most displays.
+    Srgb,
+    /// The ITU-R BT.709 transfer function, used by HD video.
+    Bt709,
+}
+
 #[derive(Component, Debug)]
 pub struct ExtractedCamera {
     pub target: Option<NormalizedRenderTarget>,
@@ -801,6 +898,8 @@ pub struct ExtractedCamera {
     pub clear_color: ClearColorConfig,
     pub sorted_camera_index_for_target: usize,
     pub exposure: f32,
+    /// The [`ScissorRect`] of the camera in physical pixels of its main textures, clamped to them.
+    pub scissor: Option<URect>,
 }
 
 pub fn extract_cameras(
@@ -818,6 +917,8 @@ pub fn extract_cameras(
             Option<&TemporalJitter>,
             Option<&RenderLayers>,
             Option<&Projection>,
+            Option<&RenderScale>,
+            Option<&ScissorRect>,
         )>,
     >,
     primary_window: Extract<Query<Entity, With<PrimaryWindow>>>,
@@ -835,6 +936,8 @@ pub fn extract_cameras(
         temporal_jitter,
         render_layers,
         projection,
+        render_scale,
+        scissor_rect,
     ) in query.iter()
     {
         let color_grading = *color_grading.unwrap_or(&ColorGrading::default());
@@ -843,28 +946,48 @@ pub fn extract_cameras(
             continue;
         }
 
-        if let (
-            Some(URect {
-                min: viewport_origin,
-                ..
-            }),
-            Some(viewport_size),
-            Some(target_size),
-        ) = (
+        if let (Some(viewport_rect), Some(target_size)) = (
             camera.physical_viewport_rect(),
-            camera.physical_viewport_size(),
             camera.physical_target_size(),
         ) {
-            if target_size.x == 0 || target_size.y == 0 {
+            // Cameras with a zero-area viewport, for example during a collapse animation, aren't
+            // extracted, so no passes run for them until their viewport has an area again.
+            if target_size.x == 0 || target_size.y == 0 || viewport_rect.is_empty() {
                 continue;
             }
 
+            let mut viewport = camera.viewport.clone();
+            let mut scissor = scissor_rect.map(|scissor| {
+                URect::from_corners(
+                    scissor.physical_position,
+                    scissor.physical_position + scissor.physical_size,
+                )
+                .intersect(URect::from_corners(UVec2::ZERO, target_size))
+            });
+            let (viewport_rect, target_size) = match render_scale {
+                Some(render_scale) => {
+                    let (scaled_rect, scaled_target_size) =
+                        render_scale.scale_viewport(viewport_rect, target_size);
+                    if let Some(viewport) = &mut viewport {
+                        viewport.physical_position = scaled_rect.min;
+                        viewport.physical_size = scaled_rect.size();
+                    }
+                    if let Some(scissor) = &mut scissor {
+                        *scissor = render_scale.scale_viewport(*scissor, target_size).0;
+                    }
+                    (scaled_rect, scaled_target_size)
+                }
+                None => (viewport_rect, target_size),
+            };
+            let viewport_origin = viewport_rect.min;
+            let viewport_size = viewport_rect.size();
+
             let mut commands = commands.get_or_spawn(entity);
 
             commands.insert((
                 ExtractedCamera {
                     target: camera.target.normalize(primary_window),
-                    viewport: camera.viewport.clone(),
+                    viewport,
                     physical_viewport_size: Some(viewport_size),
                     physical_target_size: Some(target_size),
                     render_graph: camera_render_graph.0,
@@ -877,6 +1000,7 @@ pub fn extract_cameras(
                     exposure: exposure
                         .map(|e| e.exposure())
                         .unwrap_or_else(|| Exposure::default().exposure()),
+                    scissor,
                 },
                 ExtractedView {
                     projection: camera.projection_matrix(),
@@ -1005,3 +1129,122 @@ impl TemporalJitter {
 /// Often used in conjunction with antialiasing post-process effects to reduce textures blurriness.
 #[derive(Component)]
 pub struct MipBias(pub f32);
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        extract_cameras, Camera, CameraRenderGraph, ExtractedCamera, RenderScale, RenderTargetInfo,
+        ScissorRect, Viewport,
+    };
+    use crate::{
+        primitives::Frustum, render_graph::RenderSubGraph, view::VisibleEntities, MainWorld,
+    };
+    use bevy_ecs::prelude::*;
+    use bevy_math::{URect, UVec2};
+    use bevy_transform::components::GlobalTransform;
+
+    #[derive(RenderSubGraph, Debug, Clone, PartialEq, Eq, Hash)]
+    struct TestGraph;
+
+    fn spawn_camera(world: &mut World, viewport: Option<Viewport>) -> Entity {
+        let mut camera = Camera {
+            viewport,
+            ..Default::default()
+        };
+        camera.computed.target_info = Some(RenderTargetInfo {
+            physical_size: UVec2::new(800, 600),
+            scale_factor: 1.0,
+        });
+        world
+            .spawn((
+                camera,
+                CameraRenderGraph::new(TestGraph),
+                GlobalTransform::default(),
+                VisibleEntities::default(),
+                Frustum::default(),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn render_scale_shrinks_only_the_scaled_camera() {
+        let target_size = UVec2::new(1920, 1080);
+        let full = URect::from_corners(UVec2::ZERO, target_size);
+
+        // A UI camera without `RenderScale` is extracted unchanged.
+        let (rect, size) = RenderScale::default().scale_viewport(full, target_size);
+        assert_eq!((rect, size), (full, target_size));
+
+        let (rect, size) = RenderScale(0.7).scale_viewport(full, target_size);
+        assert_eq!(size, UVec2::new(1344, 756));
+        assert_eq!(rect, URect::from_corners(UVec2::ZERO, size));
+
+        // Viewports stay inside the smaller target.
+        let right_half = URect::new(961, 0, 1920, 1080);
+        let (rect, size) = RenderScale(0.5).scale_viewport(right_half, target_size);
+        assert_eq!(size, UVec2::new(960, 540));
+        assert_eq!(rect, URect::new(480, 0, 960, 540));
+        assert_eq!(RenderScale(0.0001).scale(target_size), UVec2::ONE);
+    }
+
+    #[test]
+    fn cameras_with_zero_viewport_area_are_not_extracted() {
+        let mut main_world = World::new();
+        let visible = spawn_camera(&mut main_world, None);
+        let collapsed = spawn_camera(
+            &mut main_world,
+            Some(Viewport {
+                physical_position: UVec2::new(100, 100),
+                physical_size: UVec2::new(0, 300),
+                ..Default::default()
+            }),
+        );
+
+        let mut render_world = World::new();
+        render_world.insert_resource(MainWorld(main_world));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(extract_cameras);
+        schedule.run(&mut render_world);
+
+        assert!(render_world.get::<ExtractedCamera>(visible).is_some());
+        assert!(render_world.get_entity(collapsed).is_none());
+
+        // The camera is extracted again once its viewport has an area.
+        let mut main_world = render_world.remove_resource::<MainWorld>().unwrap();
+        let mut camera = main_world.get_mut::<Camera>(collapsed).unwrap();
+        camera.viewport.as_mut().unwrap().physical_size = UVec2::new(200, 300);
+        render_world.insert_resource(main_world);
+        schedule.run(&mut render_world);
+        assert!(render_world.get::<ExtractedCamera>(collapsed).is_some());
+    }
+
+    #[test]
+    fn scissor_rect_is_clamped_and_scaled_to_the_main_textures() {
+        let mut main_world = World::new();
+        let unclipped = spawn_camera(&mut main_world, None);
+        let clamped = spawn_camera(&mut main_world, None);
+        main_world.entity_mut(clamped).insert(ScissorRect {
+            physical_position: UVec2::new(700, 500),
+            physical_size: UVec2::new(200, 200),
+        });
+        let scaled = spawn_camera(&mut main_world, None);
+        main_world.entity_mut(scaled).insert((
+            ScissorRect {
+                physical_position: UVec2::new(100, 100),
+                physical_size: UVec2::new(200, 200),
+            },
+            RenderScale(0.5),
+        ));
+
+        let mut render_world = World::new();
+        render_world.insert_resource(MainWorld(main_world));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(extract_cameras);
+        schedule.run(&mut render_world);
+
+        let scissor = |entity| render_world.get::<ExtractedCamera>(entity).unwrap().scissor;
+        assert_eq!(scissor(unclipped), None);
+        assert_eq!(scissor(clamped), Some(URect::new(700, 500, 800, 600)));
+        assert_eq!(scissor(scaled), Some(URect::new(50, 50, 150, 150)));
+    }
+}
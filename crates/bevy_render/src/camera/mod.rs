@@ -13,7 +13,7 @@ pub use projection::*;
 
 use crate::{
     extract_component::ExtractComponentPlugin, extract_resource::ExtractResourcePlugin,
-    render_graph::RenderGraph, ExtractSchedule, Render, RenderApp, RenderSet,
+    render_graph::RenderGraph, ExtractSchedule, ExtractSet, Render, RenderApp, RenderSet,
 };
 use bevy_app::{App, Plugin};
 use bevy_ecs::schedule::IntoSystemConfigs;
@@ -32,6 +32,10 @@ impl Plugin for CameraPlugin {
             .register_type::<ClearColorConfig>()
             .register_type::<CameraRenderGraph>()
             .register_type::<CameraMainTextureUsages>()
+            .register_type::<ManualMsaaResolve>()
+            .register_type::<RenderScale>()
+            .register_type::<ScissorRect>()
+            .register_type::<OutputColorSpace>()
             .register_type::<Exposure>()
             .init_resource::<ManualTextureViews>()
             .init_resource::<ClearColor>()
@@ -42,12 +46,14 @@ impl Plugin for CameraPlugin {
                 ExtractResourcePlugin::<ManualTextureViews>::default(),
                 ExtractResourcePlugin::<ClearColor>::default(),
                 ExtractComponentPlugin::<CameraMainTextureUsages>::default(),
+                ExtractComponentPlugin::<ManualMsaaResolve>::default(),
+                ExtractComponentPlugin::<OutputColorSpace>::default(),
             ));
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<SortedCameras>()
-                .add_systems(ExtractSchedule, extract_cameras)
+                .add_systems(ExtractSchedule, extract_cameras.in_set(ExtractSet::Cameras))
                 .add_systems(Render, sort_cameras.in_set(RenderSet::ManageViews));
             let camera_driver_node = CameraDriverNode::new(&mut render_app.world);
             let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
@@ -5,7 +5,11 @@ use bevy_ecs::{
     system::{ReadOnlySystemParam, SystemMeta, SystemParam, SystemParamItem, SystemState},
     world::unsafe_world_cell::UnsafeWorldCell,
 };
-use std::ops::{Deref, DerefMut};
+use bevy_utils::HashMap;
+use std::{
+    any::TypeId,
+    ops::{Deref, DerefMut},
+};
 
 /// A helper for accessing [`MainWorld`] content using a system parameter.
 ///
@@ -48,6 +52,70 @@ where
     item: SystemParamItem<'w, 's, P>,
 }
 
+/// Component and resource types of the [`MainWorld`] that [`Extract`] params agree not to access.
+///
+/// This resource lives in the main world. In debug builds, initializing an [`Extract`] param that
+/// reads one of the filtered types panics, naming the type and the offending system. Release
+/// builds don't check the filter.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::ExtractFilter;
+/// # struct EditorState;
+/// # let mut world = World::new();
+/// # world.init_resource::<ExtractFilter>();
+/// world.resource_mut::<ExtractFilter>().filter::<EditorState>();
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct ExtractFilter {
+    filtered: HashMap<TypeId, &'static str>,
+}
+
+impl ExtractFilter {
+    /// Adds the component or resource type `T` to the filter.
+    pub fn filter<T: 'static>(&mut self) -> &mut Self {
+        self.filtered
+            .insert(TypeId::of::<T>(), std::any::type_name::<T>());
+        self
+    }
+
+    /// Returns `true` if `T` was added to the filter.
+    pub fn is_filtered<T: 'static>(&self) -> bool {
+        self.filtered.contains_key(&TypeId::of::<T>())
+    }
+}
+
+/// Panics if `P` accesses a type listed in the [`ExtractFilter`] of the main `world`.
+#[cfg(debug_assertions)]
+fn assert_unfiltered<P: ReadOnlySystemParam + 'static>(world: &mut World, system_name: &str) {
+    use bevy_ecs::system::StaticSystemParam;
+
+    if world
+        .get_resource::<ExtractFilter>()
+        .map_or(true, |filter| filter.filtered.is_empty())
+    {
+        return;
+    }
+
+    let mut system = IntoSystem::into_system(|_: StaticSystemParam<P>| {});
+    system.initialize(world);
+    let access = system.component_access();
+    let components = world.components();
+    for (type_id, type_name) in &world.resource::<ExtractFilter>().filtered {
+        let accessed = [
+            components.get_id(*type_id),
+            components.get_resource_id(*type_id),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|id| access.has_read(id));
+        assert!(
+            !accessed,
+            "`{system_name}` extracts `{type_name}`, which is excluded by the `ExtractFilter`"
+        );
+    }
+}
+
 #[doc(hidden)]
 pub struct ExtractState<P: SystemParam + 'static> {
     state: SystemState<P>,
@@ -68,6 +136,8 @@ where
 
     fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
         let mut main_world = world.resource_mut::<MainWorld>();
+        #[cfg(debug_assertions)]
+        assert_unfiltered::<P>(&mut main_world, system_meta.name());
         ExtractState {
             state: SystemState::new(&mut main_world),
             main_world_state: Res::<MainWorld>::init_state(world, system_meta),
@@ -130,3 +200,51 @@ where
         (&self.item).into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Extract, ExtractFilter};
+    use crate::MainWorld;
+    use bevy_ecs::prelude::*;
+
+    #[derive(Resource, Default)]
+    struct Extracted;
+
+    #[derive(Resource, Default)]
+    struct Filtered;
+
+    fn render_world() -> World {
+        let mut main_world = World::new();
+        main_world.init_resource::<Extracted>();
+        main_world.init_resource::<Filtered>();
+        main_world.init_resource::<ExtractFilter>();
+        main_world
+            .resource_mut::<ExtractFilter>()
+            .filter::<Filtered>();
+        assert!(main_world
+            .resource::<ExtractFilter>()
+            .is_filtered::<Filtered>());
+
+        let mut render_world = World::new();
+        render_world.insert_resource(MainWorld(main_world));
+        render_world
+    }
+
+    #[test]
+    fn unfiltered_types_can_be_extracted() {
+        let mut render_world = render_world();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(|_: Extract<Res<Extracted>>| {});
+        schedule.run(&mut render_world);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "excluded by the `ExtractFilter`")]
+    fn extracting_a_filtered_type_panics() {
+        let mut render_world = render_world();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(|_: Extract<Res<Filtered>>| {});
+        schedule.run(&mut render_world);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{
+    render_resource::{Buffer, BufferAsyncError, BufferDescriptor, BufferUsages, MapMode},
+    renderer::{render_system, RenderDevice, RenderQueue},
+    settings::WgpuFeatures,
+    Render, RenderApp, RenderSet,
+};
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, First, Plugin};
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::prelude::*;
+use bevy_utils::tracing::warn;
+use std::sync::{Arc, Mutex};
+use wgpu::{QuerySet, QuerySetDescriptor, QueryType};
+
+const TIMESTAMPS_SIZE: u64 = (2 * std::mem::size_of::<u64>()) as u64;
+
+/// Adds a "GPU frame time" diagnostic, measuring how long the GPU spends on the work submitted
+/// by the renderer during a frame.
+///
+/// The work is bracketed with timestamp queries, whose results are read back a frame or more
+/// later and added to the [`DiagnosticsStore`](bevy_diagnostic::DiagnosticsStore) in
+/// milliseconds, so that they show up in diagnostics overlays and the
+/// [`LogDiagnosticsPlugin`](bevy_diagnostic::LogDiagnosticsPlugin).
+///
+/// This requires [`WgpuFeatures::TIMESTAMP_QUERY`], since the timestamps are written on the
+/// command encoder rather than inside a pass. If the render device doesn't support it, the plugin
+/// logs a warning and the diagnostic never receives a measurement.
+#[derive(Default)]
+pub struct GpuFrameTimeDiagnosticsPlugin;
+
+impl Plugin for GpuFrameTimeDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = async_channel::bounded(1);
+        app.register_diagnostic(Diagnostic::new(Self::GPU_FRAME_TIME).with_suffix("ms"))
+            .insert_resource(GpuFrameTimeReceiver(receiver))
+            .add_systems(First, receive_gpu_frame_time);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(GpuFrameTimeSender(sender))
+                .add_systems(
+                    Render,
+                    (
+                        begin_gpu_frame_time
+                            .in_set(RenderSet::Render)
+                            .before(render_system),
+                        end_gpu_frame_time
+                            .in_set(RenderSet::Render)
+                            .after(render_system),
+                        read_gpu_frame_time.in_set(RenderSet::Cleanup),
+                    )
+                        .run_if(resource_exists::<GpuFrameTimeQueries>),
+                );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        let render_device = render_app.world.resource::<RenderDevice>();
+        if !render_device
+            .features()
+            .contains(WgpuFeatures::TIMESTAMP_QUERY)
+        {
+            warn!("GpuFrameTimeDiagnosticsPlugin requires WgpuFeatures::TIMESTAMP_QUERY, which is not supported by the render device");
+            return;
+        }
+
+        let timestamp_period = render_app
+            .world
+            .resource::<RenderQueue>()
+            .get_timestamp_period();
+        let queries = GpuFrameTimeQueries::new(render_device, timestamp_period);
+        render_app.insert_resource(queries);
+    }
+}
+
+impl GpuFrameTimeDiagnosticsPlugin {
+    pub const GPU_FRAME_TIME: DiagnosticPath = DiagnosticPath::const_new("render/gpu_frame_time");
+}
+
+#[derive(Resource)]
+struct GpuFrameTimeReceiver(Receiver<f64>);
+
+#[derive(Resource)]
+struct GpuFrameTimeSender(Sender<f64>);
+
+fn receive_gpu_frame_time(receiver: Res<GpuFrameTimeReceiver>, mut diagnostics: Diagnostics) {
+    if let Ok(frame_time) = receiver.0.try_recv() {
+        diagnostics.add_measurement(&GpuFrameTimeDiagnosticsPlugin::GPU_FRAME_TIME, || {
+            frame_time
+        });
+    }
+}
+
+/// Converts the timestamps written at the start and end of a frame to milliseconds, given the
+/// number of nanoseconds per timestamp tick.
+fn frame_time_ms([start, end]: [u64; 2], timestamp_period: f32) -> f64 {
+    end.saturating_sub(start) as f64 * timestamp_period as f64 / 1_000_000.0
+}
+
+/// The GPU resources used to measure a frame with timestamp queries.
+#[derive(Resource)]
+struct GpuFrameTimeQueries {
+    query_set: QuerySet,
+    resolve_buffer: Buffer,
+    readback_buffer: Buffer,
+    timestamp_period: f32,
+    /// Whether the start of the current frame was timestamped.
+    measuring: bool,
+    /// Whether the timestamps of a previous frame are being read back.
+    in_flight: bool,
+    /// The result of mapping the readback buffer, set once mapping has finished.
+    map_result: Arc<Mutex<Option<Result<(), BufferAsyncError>>>>,
+}
+
+impl GpuFrameTimeQueries {
+    fn new(render_device: &RenderDevice, timestamp_period: f32) -> Self {
+        let query_set = render_device
+            .wgpu_device()
+            .create_query_set(&QuerySetDescriptor {
+                label: Some("gpu_frame_time_query_set"),
+                ty: QueryType::Timestamp,
+                count: 2,
+            });
+        let resolve_buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("gpu_frame_time_resolve_buffer"),
+            size: TIMESTAMPS_SIZE,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("gpu_frame_time_readback_buffer"),
+            size: TIMESTAMPS_SIZE,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            timestamp_period,
+            measuring: false,
+            in_flight: false,
+            map_result: Arc::default(),
+        }
+    }
+}
+
+/// Writes a timestamp before the render graph submits the work of the frame.
+fn begin_gpu_frame_time(
+    mut queries: ResMut<GpuFrameTimeQueries>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    if queries.in_flight {
+        return;
+    }
+
+    let mut encoder = render_device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("gpu_frame_time_begin_encoder"),
+    });
+    encoder.write_timestamp(&queries.query_set, 0);
+    render_queue.submit([encoder.finish()]);
+    queries.measuring = true;
+}
+
+/// Writes a timestamp after the work of the frame, then resolves both timestamps and starts
+/// mapping them.
+fn end_gpu_frame_time(
+    mut queries: ResMut<GpuFrameTimeQueries>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    if !queries.measuring {
+        return;
+    }
+    queries.measuring = false;
+
+    let mut encoder = render_device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("gpu_frame_time_end_encoder"),
+    });
+    encoder.write_timestamp(&queries.query_set, 1);
+    encoder.resolve_query_set(&queries.query_set, 0..2, &queries.resolve_buffer, 0);
+    encoder.copy_buffer_to_buffer(
+        &queries.resolve_buffer,
+        0,
+        &queries.readback_buffer,
+        0,
+        TIMESTAMPS_SIZE,
+    );
+    render_queue.submit([encoder.finish()]);
+
+    let map_result = queries.map_result.clone();
+    render_device.map_buffer(
+        &queries.readback_buffer.slice(..),
+        MapMode::Read,
+        move |result| *map_result.lock().unwrap() = Some(result),
+    );
+    queries.in_flight = true;
+}
+
+/// Sends the frame time of a previous frame to the main world once its timestamps are mapped.
+fn read_gpu_frame_time(mut queries: ResMut<GpuFrameTimeQueries>, sender: Res<GpuFrameTimeSender>) {
+    let Some(map_result) = queries.map_result.lock().unwrap().take() else {
+        return;
+    };
+    queries.in_flight = false;
+    if let Err(err) = map_result {
+        warn!("Failed to map GPU frame time buffer: {err}");
+        return;
+    }
+
+    {
+        let data = queries.readback_buffer.slice(..).get_mapped_range();
+        let mut timestamps = [0; 2];
+        for (timestamp, bytes) in timestamps.iter_mut().zip(data.chunks_exact(8)) {
+            *timestamp = u64::from_ne_bytes(bytes.try_into().unwrap());
+        }
+        // The main world may not have received the previous frame time yet, in which case this
+        // one is dropped.
+        let _ = sender
+            .0
+            .try_send(frame_time_ms(timestamps, queries.timestamp_period));
+    }
+    queries.readback_buffer.unmap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{frame_time_ms, GpuFrameTimeDiagnosticsPlugin, GpuFrameTimeReceiver};
+    use bevy_app::App;
+    use bevy_diagnostic::DiagnosticsStore;
+
+    #[test]
+    fn frame_time_is_added_to_the_diagnostics_store() {
+        assert_eq!(frame_time_ms([1_000, 2_501_000], 1.0), 2.5);
+        assert_eq!(frame_time_ms([1_000, 1_001_000], 2.0), 2.0);
+
+        let mut app = App::new();
+        app.add_plugins(GpuFrameTimeDiagnosticsPlugin);
+        let path = &GpuFrameTimeDiagnosticsPlugin::GPU_FRAME_TIME;
+        assert!(app.world.resource::<DiagnosticsStore>().get(path).is_some());
+
+        // Stand in for the render world.
+        let (sender, receiver) = async_channel::bounded(1);
+        app.insert_resource(GpuFrameTimeReceiver(receiver));
+        sender.try_send(2.5).unwrap();
+        app.update();
+
+        let store = app.world.resource::<DiagnosticsStore>();
+        assert_eq!(store.get_measurement(path).unwrap().value, 2.5);
+    }
+}
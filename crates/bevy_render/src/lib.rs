@@ -16,7 +16,9 @@ mod extract_param;
 pub mod extract_resource;
 pub mod globals;
 pub mod gpu_component_array_buffer;
+pub mod gpu_frame_time;
 pub mod mesh;
+pub mod pipeline_statistics;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod pipelined_rendering;
 pub mod primitives;
@@ -48,7 +50,7 @@ pub mod prelude {
 
 use bevy_ecs::schedule::ScheduleBuildSettings;
 use bevy_utils::prelude::default;
-pub use extract_param::Extract;
+pub use extract_param::{Extract, ExtractFilter};
 
 use bevy_hierarchy::ValidParentCheckPlugin;
 use globals::GlobalsPlugin;
@@ -59,15 +61,17 @@ use crate::{
     camera::CameraPlugin,
     mesh::{morph::MorphPlugin, Mesh, MeshPlugin},
     render_asset::prepare_assets,
-    render_resource::{PipelineCache, Shader, ShaderLoader},
+    render_resource::{
+        update_bind_group_cache_system, BindGroupCache, PipelineCache, Shader, ShaderLoader,
+    },
     renderer::{render_system, RenderInstance},
-    settings::RenderCreation,
+    settings::{RenderCreation, RenderResources},
     view::{ViewPlugin, WindowRenderPlugin},
 };
 use bevy_app::{App, AppLabel, Plugin, SubApp};
 use bevy_asset::{load_internal_asset, AssetApp, AssetServer, Handle};
 use bevy_ecs::{prelude::*, schedule::ScheduleLabel};
-use bevy_utils::tracing::debug;
+use bevy_utils::tracing::{debug, warn};
 use std::{
     ops::{Deref, DerefMut},
     sync::{Arc, Mutex},
@@ -121,6 +125,10 @@ pub enum RenderSet {
     /// Actual rendering happens here.
     /// In most cases, only the render backend should insert resources here.
     Render,
+    /// Runs after all rendering has been submitted, but before [`Cleanup`](RenderSet::Cleanup)
+    /// despawns the render world entities. Recycle transient resources used during
+    /// [`Render`](RenderSet::Render) here, while the entities they belong to still exist.
+    PrepareCleanup,
     /// Cleanup render resources here.
     Cleanup,
 }
@@ -146,6 +154,7 @@ impl Render {
                 PhaseSort,
                 Prepare,
                 Render,
+                PrepareCleanup,
                 Cleanup,
             )
                 .chain(),
@@ -173,6 +182,45 @@ impl Render {
 #[derive(ScheduleLabel, PartialEq, Eq, Debug, Clone, Hash)]
 pub struct ExtractSchedule;
 
+impl ExtractSchedule {
+    /// Sets up the base structure of the [`ExtractSchedule`].
+    ///
+    /// The sets defined in [`ExtractSet`] are configured to run in order.
+    pub fn base_schedule() -> Schedule {
+        use ExtractSet::*;
+
+        let mut schedule = Schedule::new(Self);
+        // We skip applying any commands during the ExtractSchedule
+        // so commands can be applied on the render thread.
+        schedule.set_build_settings(ScheduleBuildSettings {
+            auto_insert_apply_deferred: false,
+            ..default()
+        });
+        schedule.set_apply_final_deferred(false);
+
+        schedule.configure_sets((Cameras, Meshes, Late).chain());
+
+        schedule
+    }
+}
+
+/// Ordered sets of the [`ExtractSchedule`], for extract systems that depend on data extracted
+/// by other plugins.
+///
+/// Commands issued during the [`ExtractSchedule`] are only applied in
+/// [`RenderSet::ExtractCommands`], so a later set can't see the entities and resources an
+/// earlier set inserted with [`Commands`]. The ordering applies to data written in place, like
+/// the render world resources accessed with [`ResMut`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub enum ExtractSet {
+    /// Extract cameras and the views derived from them.
+    Cameras,
+    /// Extract meshes and their instance data.
+    Meshes,
+    /// Runs after every other extract set.
+    Late,
+}
+
 /// The simulation [`World`] of the application, stored as a resource.
 /// This resource is only available during [`ExtractSchedule`] and not
 /// during command application of that schedule.
@@ -227,7 +275,8 @@ pub const MATHS_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(106653563
 impl Plugin for RenderPlugin {
     /// Initializes the renderer, sets up the [`RenderSet`] and creates the rendering sub-app.
     fn build(&self, app: &mut App) {
-        app.init_resource::<DeterministicRenderingConfig>();
+        app.init_resource::<DeterministicRenderingConfig>()
+            .init_resource::<ExtractFilter>();
 
         app.init_asset::<Shader>()
             .init_asset_loader::<ShaderLoader>();
@@ -247,6 +296,39 @@ impl Plugin for RenderPlugin {
                 // SAFETY: Plugins should be set up on the main thread.
                 unsafe { initialize_render_app(app) };
             }
+            RenderCreation::ManualAsync(future) => {
+                let future_renderer_resources_wrapper = Arc::new(Mutex::new(None));
+                app.insert_resource(FutureRendererResources(
+                    future_renderer_resources_wrapper.clone(),
+                ));
+
+                // In wasm, spawn a task and detach it for execution
+                #[cfg(target_arch = "wasm32")]
+                {
+                    let future = future.lock().unwrap().take().expect(
+                        "RenderCreation::ManualAsync can only be used to build the RenderPlugin once",
+                    );
+                    let async_renderer = async move {
+                        let RenderResources(device, queue, adapter_info, adapter, instance) =
+                            future.await;
+                        *future_renderer_resources_wrapper.lock().unwrap() =
+                            Some((device, queue, adapter_info, adapter, instance));
+                    };
+                    bevy_tasks::IoTaskPool::get()
+                        .spawn_local(async_renderer)
+                        .detach();
+                }
+                // Otherwise, the future is polled by `ready` and `finish`, so that the main
+                // thread isn't blocked while it's pending.
+                #[cfg(not(target_arch = "wasm32"))]
+                assert!(
+                    future.lock().unwrap().is_some(),
+                    "RenderCreation::ManualAsync can only be used to build the RenderPlugin once"
+                );
+
+                // SAFETY: Plugins should be set up on the main thread.
+                unsafe { initialize_render_app(app) };
+            }
             RenderCreation::Automatic(render_creation) => {
                 if let Some(backends) = &render_creation.backends {
                     let future_renderer_resources_wrapper = Arc::new(Mutex::new(None));
@@ -256,12 +338,17 @@ impl Plugin for RenderPlugin {
 
                     let settings = render_creation.clone();
                     let async_renderer = async move {
-                        let (instance, adapter) = renderer::create_instance_and_adapter(
-                            backends, &settings,
-                        )
-                        .expect(
-                            "Unable to find a GPU! Make sure you have installed required drivers!",
-                        );
+                        let Some((instance, adapter)) =
+                            renderer::create_instance_and_adapter(backends, &settings)
+                        else {
+                            if settings.fallback_to_headless && !cfg!(target_arch = "wasm32") {
+                                warn!("Unable to find a GPU, running without rendering since `WgpuSettings::fallback_to_headless` is set");
+                                return;
+                            }
+                            panic!(
+                                "Unable to find a GPU! Make sure you have installed required drivers!"
+                            );
+                        };
 
                         let (device, queue, adapter_info, render_adapter) =
                             renderer::initialize_renderer(adapter, &settings).await;
@@ -284,10 +371,26 @@ impl Plugin for RenderPlugin {
                         .detach();
                     // Otherwise, just block for it to complete
                     #[cfg(not(target_arch = "wasm32"))]
-                    futures_lite::future::block_on(async_renderer);
-
-                    // SAFETY: Plugins should be set up on the main thread.
-                    unsafe { initialize_render_app(app) };
+                    {
+                        futures_lite::future::block_on(async_renderer);
+                        if app
+                            .world
+                            .resource::<FutureRendererResources>()
+                            .0
+                            .lock()
+                            .unwrap()
+                            .is_none()
+                        {
+                            // No adapter was found and `fallback_to_headless` is set, so the app
+                            // is set up without a render sub-app.
+                            app.world.remove_resource::<FutureRendererResources>();
+                        }
+                    }
+
+                    if app.world.contains_resource::<FutureRendererResources>() {
+                        // SAFETY: Plugins should be set up on the main thread.
+                        unsafe { initialize_render_app(app) };
+                    }
                 }
             }
         };
@@ -310,6 +413,9 @@ impl Plugin for RenderPlugin {
     }
 
     fn ready(&self, app: &App) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.poll_render_resources(app, false);
+
         app.world
             .get_resource::<FutureRendererResources>()
             .and_then(|frr| frr.0.try_lock().map(|locked| locked.is_some()).ok())
@@ -318,6 +424,10 @@ impl Plugin for RenderPlugin {
 
     fn finish(&self, app: &mut App) {
         load_internal_asset!(app, MATHS_SHADER_HANDLE, "maths.wgsl", Shader::from_wgsl);
+        // `finish` can be called without waiting for `ready`.
+        #[cfg(not(target_arch = "wasm32"))]
+        self.poll_render_resources(app, true);
+
         if let Some(future_renderer_resources) =
             app.world.remove_resource::<FutureRendererResources>()
         {
@@ -345,6 +455,35 @@ impl Plugin for RenderPlugin {
     }
 }
 
+impl RenderPlugin {
+    /// Polls the future of a [`RenderCreation::ManualAsync`] once, or until it resolves if
+    /// `block` is `true`, and stores the resources it resolved to for `finish`.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn poll_render_resources(&self, app: &App, block: bool) {
+        let RenderCreation::ManualAsync(future) = &self.render_creation else {
+            return;
+        };
+        let Some(future_renderer_resources) = app.world.get_resource::<FutureRendererResources>()
+        else {
+            return;
+        };
+        let mut future = future.lock().unwrap();
+        let Some(pending) = future.as_mut() else {
+            return;
+        };
+        let resources = if block {
+            Some(futures_lite::future::block_on(pending))
+        } else {
+            bevy_utils::futures::check_ready(pending)
+        };
+        if let Some(RenderResources(device, queue, adapter_info, adapter, instance)) = resources {
+            *future = None;
+            *future_renderer_resources.0.lock().unwrap() =
+                Some((device, queue, adapter_info, adapter, instance));
+        }
+    }
+}
+
 /// A "scratch" world used to avoid allocating new worlds every frame when
 /// swapping out the [`MainWorld`] for [`ExtractSchedule`].
 #[derive(Resource, Default)]
@@ -373,19 +512,11 @@ unsafe fn initialize_render_app(app: &mut App) {
     let mut render_app = App::empty();
     render_app.main_schedule_label = Render.intern();
 
-    let mut extract_schedule = Schedule::new(ExtractSchedule);
-    // We skip applying any commands during the ExtractSchedule
-    // so commands can be applied on the render thread.
-    extract_schedule.set_build_settings(ScheduleBuildSettings {
-        auto_insert_apply_deferred: false,
-        ..default()
-    });
-    extract_schedule.set_apply_final_deferred(false);
-
     render_app
-        .add_schedule(extract_schedule)
+        .add_schedule(ExtractSchedule::base_schedule())
         .add_schedule(Render::base_schedule())
         .init_resource::<render_graph::RenderGraph>()
+        .init_resource::<BindGroupCache>()
         .insert_resource(app.world.resource::<AssetServer>().clone())
         .add_systems(ExtractSchedule, PipelineCache::extract_shaders)
         .add_systems(
@@ -400,6 +531,7 @@ unsafe fn initialize_render_app(app: &mut App) {
                 )
                     .in_set(RenderSet::Render),
                 World::clear_entities.in_set(RenderSet::Cleanup),
+                update_bind_group_cache_system.in_set(RenderSet::Cleanup),
             ),
         );
 
@@ -451,3 +583,152 @@ fn apply_extract_commands(render_world: &mut World) {
             .apply_deferred(render_world);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{ExtractSchedule, ExtractSet, Render, RenderSet};
+    use bevy_ecs::prelude::*;
+
+    #[derive(Resource, Default)]
+    struct RanSets(Vec<RenderSet>);
+
+    #[test]
+    fn prepare_cleanup_runs_between_render_and_cleanup() {
+        let mut world = World::new();
+        world.init_resource::<RanSets>();
+
+        let mut schedule = Render::base_schedule();
+        // Added in reverse so that only the set ordering determines the order they run in.
+        for set in [
+            RenderSet::Cleanup,
+            RenderSet::PrepareCleanup,
+            RenderSet::Render,
+        ] {
+            let ran = set.clone();
+            schedule.add_systems(
+                (move |mut ran_sets: ResMut<RanSets>| ran_sets.0.push(ran.clone())).in_set(set),
+            );
+        }
+        schedule.run(&mut world);
+
+        assert_eq!(
+            world.resource::<RanSets>().0,
+            [
+                RenderSet::Render,
+                RenderSet::PrepareCleanup,
+                RenderSet::Cleanup
+            ]
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct RanExtractSets(Vec<ExtractSet>);
+
+    #[test]
+    fn extract_sets_run_in_order() {
+        let mut world = World::new();
+        world.init_resource::<RanExtractSets>();
+
+        let mut schedule = ExtractSchedule::base_schedule();
+        for set in [ExtractSet::Late, ExtractSet::Meshes, ExtractSet::Cameras] {
+            let ran = set.clone();
+            schedule.add_systems(
+                (move |mut ran_sets: ResMut<RanExtractSets>| ran_sets.0.push(ran.clone()))
+                    .in_set(set),
+            );
+        }
+        schedule.run(&mut world);
+
+        assert_eq!(
+            world.resource::<RanExtractSets>().0,
+            [ExtractSet::Cameras, ExtractSet::Meshes, ExtractSet::Late]
+        );
+    }
+
+    #[test]
+    fn missing_adapter_falls_back_to_headless() {
+        use crate::{
+            settings::{RenderCreation, WgpuSettings},
+            texture::ImagePlugin,
+            RenderApp, RenderPlugin,
+        };
+        use bevy_app::App;
+        use bevy_asset::AssetPlugin;
+        use bevy_core::TaskPoolPlugin;
+        use bevy_window::{ExitCondition, WindowPlugin};
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..Default::default()
+            },
+            RenderPlugin {
+                // Without any backend to try, no adapter can be found.
+                render_creation: RenderCreation::Automatic(WgpuSettings {
+                    backends: Some(Vec::new()),
+                    fallback_to_headless: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ImagePlugin::default(),
+        ));
+
+        assert!(app.get_sub_app(RenderApp).is_err());
+        app.finish();
+        app.cleanup();
+        app.update();
+        app.update();
+    }
+
+    #[test]
+    fn manual_async_render_creation_does_not_block() {
+        use crate::{
+            renderer::{headless_render_resources, RenderDevice},
+            settings::RenderCreation,
+            texture::ImagePlugin,
+            RenderPlugin,
+        };
+        use bevy_app::{App, PluginsState};
+        use bevy_asset::AssetPlugin;
+        use bevy_core::TaskPoolPlugin;
+        use bevy_window::{ExitCondition, WindowPlugin};
+
+        let Some(resources) = headless_render_resources() else {
+            return;
+        };
+        let (sender, receiver) = async_channel::bounded(1);
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..Default::default()
+            },
+            RenderPlugin {
+                render_creation: RenderCreation::manual_async(async move {
+                    receiver.recv().await.unwrap()
+                }),
+                ..Default::default()
+            },
+            ImagePlugin::default(),
+        ));
+
+        // Building the plugin returned even though the future is still pending.
+        assert_eq!(app.plugins_state(), PluginsState::Adding);
+        assert_eq!(app.plugins_state(), PluginsState::Adding);
+
+        sender.try_send(resources).ok().unwrap();
+        assert_eq!(app.plugins_state(), PluginsState::Ready);
+        app.finish();
+        app.cleanup();
+        assert!(app.world.contains_resource::<RenderDevice>());
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{
+    render_phase::TrackedRenderPass,
+    render_resource::{Buffer, BufferAsyncError, BufferDescriptor, BufferUsages, MapMode},
+    renderer::{RenderDevice, RenderQueue},
+    settings::WgpuFeatures,
+    Render, RenderApp, RenderSet,
+};
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, First, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_utils::tracing::warn;
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex},
+};
+use wgpu::{PipelineStatisticsTypes, QuerySet, QuerySetDescriptor, QueryType};
+
+/// The statistics collected for each measured pass, in the order wgpu writes them.
+const STATISTICS: PipelineStatisticsTypes = PipelineStatisticsTypes::VERTEX_SHADER_INVOCATIONS
+    .union(PipelineStatisticsTypes::CLIPPER_INVOCATIONS)
+    .union(PipelineStatisticsTypes::CLIPPER_PRIMITIVES_OUT)
+    .union(PipelineStatisticsTypes::FRAGMENT_SHADER_INVOCATIONS);
+const STATISTICS_COUNT: usize = 4;
+const RESULTS_SIZE: u64 = (STATISTICS_COUNT * std::mem::size_of::<u64>()) as u64;
+
+/// Collects GPU pipeline statistics for a single render pass per frame.
+///
+/// Passes are measured by wrapping their draw calls in [`PipelineStatisticsQueries::measure`].
+/// Results are read back a frame or more later and written to the [`PipelineStatistics`]
+/// resource in the main world.
+///
+/// This requires [`WgpuFeatures::PIPELINE_STATISTICS_QUERY`]. If the render device doesn't
+/// support it, the plugin logs a warning and [`PipelineStatistics`] keeps its default value.
+pub struct PipelineStatisticsPlugin;
+
+impl Plugin for PipelineStatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = async_channel::bounded(1);
+        app.init_resource::<PipelineStatistics>()
+            .insert_resource(PipelineStatisticsReceiver(receiver))
+            .add_systems(First, receive_pipeline_statistics);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(PipelineStatisticsSender(sender))
+                .add_systems(
+                    Render,
+                    (read_pipeline_statistics, resolve_pipeline_statistics)
+                        .chain()
+                        .in_set(RenderSet::Cleanup)
+                        .run_if(resource_exists::<PipelineStatisticsQueries>),
+                );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        let render_device = render_app.world.resource::<RenderDevice>();
+        if !render_device
+            .features()
+            .contains(WgpuFeatures::PIPELINE_STATISTICS_QUERY)
+        {
+            warn!("PipelineStatisticsPlugin requires WgpuFeatures::PIPELINE_STATISTICS_QUERY, which is not supported by the render device");
+            return;
+        }
+
+        let queries = PipelineStatisticsQueries::new(render_device);
+        render_app.insert_resource(queries);
+    }
+}
+
+/// Pipeline statistics of the most recently measured render pass.
+///
+/// See [`PipelineStatisticsPlugin`].
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PipelineStatistics {
+    /// The label of the measured pass, or `None` if no pass has been measured yet.
+    pub pass: Option<Cow<'static, str>>,
+    /// The number of times the vertex shader was invoked.
+    pub vertex_shader_invocations: u64,
+    /// The number of primitives sent to the clipper.
+    pub clipper_invocations: u64,
+    /// The number of primitives output by the clipper.
+    pub clipper_primitives_out: u64,
+    /// The number of times the fragment shader was invoked.
+    pub fragment_shader_invocations: u64,
+}
+
+impl PipelineStatistics {
+    fn from_query_results(pass: Cow<'static, str>, results: &[u64; STATISTICS_COUNT]) -> Self {
+        Self {
+            pass: Some(pass),
+            vertex_shader_invocations: results[0],
+            clipper_invocations: results[1],
+            clipper_primitives_out: results[2],
+            fragment_shader_invocations: results[3],
+        }
+    }
+
+    /// The number of primitives discarded by the clipper.
+    pub fn primitives_clipped(&self) -> u64 {
+        self.clipper_invocations
+            .saturating_sub(self.clipper_primitives_out)
+    }
+}
+
+#[derive(Resource)]
+struct PipelineStatisticsReceiver(Receiver<PipelineStatistics>);
+
+#[derive(Resource)]
+struct PipelineStatisticsSender(Sender<PipelineStatistics>);
+
+fn receive_pipeline_statistics(
+    receiver: Res<PipelineStatisticsReceiver>,
+    mut statistics: ResMut<PipelineStatistics>,
+) {
+    if let Ok(received) = receiver.0.try_recv() {
+        *statistics = received;
+    }
+}
+
+/// The GPU resources used to measure a render pass with a pipeline statistics query.
+///
+/// This resource only exists in the render world when the render device supports
+/// [`WgpuFeatures::PIPELINE_STATISTICS_QUERY`].
+#[derive(Resource)]
+pub struct PipelineStatisticsQueries {
+    query_set: QuerySet,
+    resolve_buffer: Buffer,
+    readback_buffer: Buffer,
+    /// The pass measured during the current frame.
+    measured: Mutex<Option<Cow<'static, str>>>,
+    /// The pass whose results are being read back.
+    in_flight: Option<Cow<'static, str>>,
+    /// The result of mapping the readback buffer, set once mapping has finished.
+    map_result: Arc<Mutex<Option<Result<(), BufferAsyncError>>>>,
+}
+
+impl PipelineStatisticsQueries {
+    fn new(render_device: &RenderDevice) -> Self {
+        let query_set = render_device
+            .wgpu_device()
+            .create_query_set(&QuerySetDescriptor {
+                label: Some("pipeline_statistics_query_set"),
+                ty: QueryType::PipelineStatistics(STATISTICS),
+                count: 1,
+            });
+        let resolve_buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("pipeline_statistics_resolve_buffer"),
+            size: RESULTS_SIZE,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("pipeline_statistics_readback_buffer"),
+            size: RESULTS_SIZE,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            measured: Mutex::new(None),
+            in_flight: None,
+            map_result: Arc::default(),
+        }
+    }
+
+    /// Runs `draw` on `pass`, measuring the draw calls it records with a pipeline statistics query.
+    ///
+    /// Only one pass is measured per frame, and none while the previous results are still being
+    /// read back. In those cases `draw` runs without being measured.
+    pub fn measure<'a>(
+        &'a self,
+        label: impl Into<Cow<'static, str>>,
+        pass: &mut TrackedRenderPass<'a>,
+        draw: impl FnOnce(&mut TrackedRenderPass<'a>),
+    ) {
+        let mut measured = self.measured.lock().unwrap();
+        if measured.is_some() || self.in_flight.is_some() {
+            drop(measured);
+            draw(pass);
+            return;
+        }
+        *measured = Some(label.into());
+        drop(measured);
+
+        pass.begin_pipeline_statistics_query(&self.query_set, 0);
+        draw(pass);
+        pass.end_pipeline_statistics_query();
+    }
+}
+
+/// Sends the results of a previous frame's query to the main world once they are mapped.
+fn read_pipeline_statistics(
+    mut queries: ResMut<PipelineStatisticsQueries>,
+    sender: Res<PipelineStatisticsSender>,
+) {
+    let Some(map_result) = queries.map_result.lock().unwrap().take() else {
+        return;
+    };
+    let Some(pass) = queries.in_flight.take() else {
+        return;
+    };
+    if let Err(err) = map_result {
+        warn!("Failed to map pipeline statistics buffer: {err}");
+        return;
+    }
+
+    {
+        let data = queries.readback_buffer.slice(..).get_mapped_range();
+        let mut results = [0; STATISTICS_COUNT];
+        for (result, bytes) in results.iter_mut().zip(data.chunks_exact(8)) {
+            *result = u64::from_ne_bytes(bytes.try_into().unwrap());
+        }
+        // The main world may not have received the previous results yet, in which case these are
+        // dropped in favor of the next ones.
+        let _ = sender
+            .0
+            .try_send(PipelineStatistics::from_query_results(pass, &results));
+    }
+    queries.readback_buffer.unmap();
+}
+
+/// Resolves the query written during this frame and starts mapping its results.
+fn resolve_pipeline_statistics(
+    mut queries: ResMut<PipelineStatisticsQueries>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let Some(pass) = queries.measured.get_mut().unwrap().take() else {
+        return;
+    };
+
+    let mut encoder = render_device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("pipeline_statistics_resolve_encoder"),
+    });
+    encoder.resolve_query_set(&queries.query_set, 0..1, &queries.resolve_buffer, 0);
+    encoder.copy_buffer_to_buffer(
+        &queries.resolve_buffer,
+        0,
+        &queries.readback_buffer,
+        0,
+        RESULTS_SIZE,
+    );
+    render_queue.submit([encoder.finish()]);
+
+    let map_result = queries.map_result.clone();
+    render_device.map_buffer(
+        &queries.readback_buffer.slice(..),
+        MapMode::Read,
+        move |result| *map_result.lock().unwrap() = Some(result),
+    );
+    queries.in_flight = Some(pass);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PipelineStatistics;
+
+    #[test]
+    fn statistics_follow_query_result_order() {
+        let statistics =
+            PipelineStatistics::from_query_results("main_pass".into(), &[30, 10, 7, 400]);
+        assert_eq!(
+            statistics,
+            PipelineStatistics {
+                pass: Some("main_pass".into()),
+                vertex_shader_invocations: 30,
+                clipper_invocations: 10,
+                clipper_primitives_out: 7,
+                fragment_shader_invocations: 400,
+            }
+        );
+        assert_eq!(statistics.primitives_clipped(), 3);
+    }
+}
@@ -7,9 +7,10 @@ use crate::{
     },
     renderer::RenderDevice,
 };
+use bevy_math::URect;
 use bevy_utils::{default, detailed_trace};
 use std::ops::Range;
-use wgpu::{IndexFormat, RenderPass};
+use wgpu::{IndexFormat, QuerySet, RenderPass};
 
 /// Tracks the state of a [`TrackedRenderPass`].
 ///
@@ -545,6 +546,18 @@ impl<'a> TrackedRenderPass<'a> {
         );
     }
 
+    /// Sets the scissor region to the given [`ExtractedCamera::scissor`](crate::camera::ExtractedCamera::scissor).
+    ///
+    /// Subsequent draw calls will discard any fragments that fall outside this region.
+    pub fn set_camera_scissor(&mut self, scissor: URect) {
+        self.set_scissor_rect(
+            scissor.min.x,
+            scissor.min.y,
+            scissor.width(),
+            scissor.height(),
+        );
+    }
+
     /// Insert a single debug marker.
     ///
     /// This is a GPU debugging feature. This has no effect on the rendering itself.
@@ -595,6 +608,28 @@ impl<'a> TrackedRenderPass<'a> {
         self.pass.pop_debug_group();
     }
 
+    /// Starts a pipeline statistics query on this pass.
+    ///
+    /// Only a single pipeline statistics query may be active at a time, and it must be ended with
+    /// [`end_pipeline_statistics_query`] before the pass ends.
+    ///
+    /// Requires [`Features::PIPELINE_STATISTICS_QUERY`](wgpu::Features::PIPELINE_STATISTICS_QUERY).
+    ///
+    /// [`end_pipeline_statistics_query`]: TrackedRenderPass::end_pipeline_statistics_query
+    pub fn begin_pipeline_statistics_query(&mut self, query_set: &'a QuerySet, query_index: u32) {
+        detailed_trace!("begin_pipeline_statistics_query: {}", query_index);
+        self.pass
+            .begin_pipeline_statistics_query(query_set, query_index);
+    }
+
+    /// Ends the pipeline statistics query started by [`begin_pipeline_statistics_query`].
+    ///
+    /// [`begin_pipeline_statistics_query`]: TrackedRenderPass::begin_pipeline_statistics_query
+    pub fn end_pipeline_statistics_query(&mut self) {
+        detailed_trace!("end_pipeline_statistics_query");
+        self.pass.end_pipeline_statistics_query();
+    }
+
     /// Sets the blend color as used by some of the blending modes.
     ///
     /// Subsequent blending tests will test against this value.
@@ -603,3 +638,159 @@ impl<'a> TrackedRenderPass<'a> {
         self.pass.set_blend_constant(wgpu::Color::from(color));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TrackedRenderPass;
+    use crate::renderer::{headless_render_device, RenderDevice};
+    use bevy_math::URect;
+    use wgpu::{
+        BufferDescriptor, BufferUsages, Color, ColorTargetState, ColorWrites,
+        CommandEncoderDescriptor, Extent3d, FragmentState, ImageCopyBuffer, ImageDataLayout,
+        LoadOp, Maintain, MapMode, MultisampleState, Operations, PrimitiveState,
+        RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor,
+        ShaderModuleDescriptor, ShaderSource, StoreOp, TextureDescriptor, TextureDimension,
+        TextureFormat, TextureUsages, VertexState,
+    };
+
+    const SIZE: u32 = 4;
+    const FULLSCREEN_GREEN: &str = r"
+        @vertex
+        fn vertex(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+            let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+            return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+        }
+
+        @fragment
+        fn fragment() -> @location(0) vec4<f32> {
+            return vec4<f32>(0.0, 1.0, 0.0, 1.0);
+        }
+    ";
+
+    fn read_pixels(render_device: &RenderDevice, buffer: &wgpu::Buffer) -> Vec<[u8; 4]> {
+        let slice = buffer.slice(..);
+        render_device.map_buffer(&slice, MapMode::Read, |result| result.unwrap());
+        render_device.poll(Maintain::Wait);
+        let padded_row = RenderDevice::align_copy_bytes_per_row(SIZE as usize * 4);
+        let data = slice.get_mapped_range();
+        data.chunks_exact(padded_row)
+            .flat_map(|row| row[..SIZE as usize * 4].chunks_exact(4))
+            .map(|pixel| pixel.try_into().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn camera_scissor_leaves_pixels_outside_unchanged() {
+        let Some((render_device, render_queue)) = headless_render_device() else {
+            return;
+        };
+        let format = TextureFormat::Rgba8Unorm;
+        let texture = render_device.create_texture(&TextureDescriptor {
+            label: None,
+            size: Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&Default::default());
+        let shader = render_device.create_shader_module(ShaderModuleDescriptor {
+            label: None,
+            source: ShaderSource::Wgsl(FULLSCREEN_GREEN.into()),
+        });
+        let pipeline = render_device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: VertexState {
+                module: &shader,
+                entry_point: "vertex",
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: &shader,
+                entry_point: "fragment",
+                targets: &[Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            multiview: None,
+        });
+        let buffer = render_device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: (RenderDevice::align_copy_bytes_per_row(SIZE as usize * 4) * SIZE as usize)
+                as u64,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder =
+            render_device.create_command_encoder(&CommandEncoderDescriptor::default());
+        // The whole texture is cleared to red, and the green triangle covering it is only drawn
+        // inside the scissor.
+        for load in [LoadOp::Clear(Color::RED), LoadOp::Load] {
+            let pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load,
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            if load == LoadOp::Load {
+                let mut pass = TrackedRenderPass::new(&render_device, pass);
+                pass.set_camera_scissor(URect::new(1, 1, 3, 3));
+                pass.set_render_pipeline(&pipeline);
+                pass.draw(0..3, 0..1);
+            }
+        }
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(
+                        RenderDevice::align_copy_bytes_per_row(SIZE as usize * 4) as u32
+                    ),
+                    rows_per_image: None,
+                },
+            },
+            texture.size(),
+        );
+        render_queue.submit([encoder.finish()]);
+
+        let pixels = read_pixels(&render_device, &buffer);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let inside = (1..3).contains(&x) && (1..3).contains(&y);
+                let expected = if inside {
+                    [0, 255, 0, 255]
+                } else {
+                    [255, 0, 0, 255]
+                };
+                assert_eq!(
+                    pixels[(y * SIZE + x) as usize],
+                    expected,
+                    "pixel ({x}, {y})"
+                );
+            }
+        }
+    }
+}
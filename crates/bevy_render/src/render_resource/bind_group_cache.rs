@@ -0,0 +1,203 @@
+use crate::{
+    render_resource::{BindGroup, BindGroupLayout, BindGroupLayoutId},
+    renderer::RenderDevice,
+};
+use bevy_ecs::{prelude::ResMut, system::Resource};
+use bevy_utils::HashMap;
+use wgpu::{BindGroupEntry, BindingResource, BufferAddress, BufferSize, Id};
+
+/// Identifies the resource bound to a single binding of a bind group.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum BindingKey {
+    Buffer(Id<wgpu::Buffer>, BufferAddress, Option<BufferSize>),
+    BufferArray(Vec<(Id<wgpu::Buffer>, BufferAddress, Option<BufferSize>)>),
+    Sampler(Id<wgpu::Sampler>),
+    SamplerArray(Vec<Id<wgpu::Sampler>>),
+    TextureView(Id<wgpu::TextureView>),
+    TextureViewArray(Vec<Id<wgpu::TextureView>>),
+}
+
+impl BindingKey {
+    /// Returns `None` for kinds of resources that can't be compared, which bypass the cache.
+    fn new(resource: &BindingResource) -> Option<Self> {
+        let key = match resource {
+            BindingResource::Buffer(binding) => {
+                BindingKey::Buffer(binding.buffer.global_id(), binding.offset, binding.size)
+            }
+            BindingResource::BufferArray(bindings) => BindingKey::BufferArray(
+                bindings
+                    .iter()
+                    .map(|binding| (binding.buffer.global_id(), binding.offset, binding.size))
+                    .collect(),
+            ),
+            BindingResource::Sampler(sampler) => BindingKey::Sampler(sampler.global_id()),
+            BindingResource::SamplerArray(samplers) => BindingKey::SamplerArray(
+                samplers.iter().map(|sampler| sampler.global_id()).collect(),
+            ),
+            BindingResource::TextureView(view) => BindingKey::TextureView(view.global_id()),
+            BindingResource::TextureViewArray(views) => {
+                BindingKey::TextureViewArray(views.iter().map(|view| view.global_id()).collect())
+            }
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+/// A bind group layout and the resources bound to each binding.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct BindGroupKey {
+    layout: BindGroupLayoutId,
+    entries: Vec<(u32, BindingKey)>,
+}
+
+/// The internal representation of a cached [`BindGroup`] used to track whether it was recently
+/// used.
+struct CachedBindGroupMeta {
+    bind_group: BindGroup,
+    frames_since_last_use: usize,
+}
+
+/// This resource caches bind groups that are created repeatedly in the rendering process, for
+/// example every frame, from the same layout and resources.
+///
+/// Bind groups are compared by their layout and the resources bound to each binding, including
+/// the offset and size of buffer bindings. The contents of the bound resources don't matter, so
+/// writing new data to a buffer doesn't require a new bind group. The label isn't compared
+/// either, so a reused bind group keeps the label it was first created with.
+#[derive(Resource, Default)]
+pub struct BindGroupCache {
+    bind_groups: HashMap<BindGroupKey, CachedBindGroupMeta>,
+}
+
+impl BindGroupCache {
+    /// Retrieves a bind group of `layout` binding `entries`. If none was created recently, a new
+    /// [`BindGroup`] is created.
+    ///
+    /// Bind groups binding a kind of resource the cache doesn't know how to compare are always
+    /// created anew and aren't cached.
+    pub fn get<'a>(
+        &mut self,
+        render_device: &RenderDevice,
+        label: impl Into<wgpu::Label<'a>>,
+        layout: &'a BindGroupLayout,
+        entries: &'a [BindGroupEntry<'a>],
+    ) -> BindGroup {
+        let Some(entry_keys) = entries
+            .iter()
+            .map(|entry| Some((entry.binding, BindingKey::new(&entry.resource)?)))
+            .collect()
+        else {
+            return render_device.create_bind_group(label, layout, entries);
+        };
+        let key = BindGroupKey {
+            layout: layout.id(),
+            entries: entry_keys,
+        };
+        let cached = self
+            .bind_groups
+            .entry(key)
+            .or_insert_with(|| CachedBindGroupMeta {
+                bind_group: render_device.create_bind_group(label, layout, entries),
+                frames_since_last_use: 0,
+            });
+        cached.frames_since_last_use = 0;
+        cached.bind_group.clone()
+    }
+
+    /// The number of cached bind groups.
+    pub fn len(&self) -> usize {
+        self.bind_groups.len()
+    }
+
+    /// Returns `true` if no bind groups are cached.
+    pub fn is_empty(&self) -> bool {
+        self.bind_groups.is_empty()
+    }
+
+    /// Updates the cache and only retains recently used bind groups.
+    pub fn update(&mut self) {
+        self.bind_groups.retain(|_, cached| {
+            cached.frames_since_last_use += 1;
+            cached.frames_since_last_use < 3
+        });
+    }
+}
+
+/// Updates the [`BindGroupCache`] to only retain recently used bind groups.
+pub fn update_bind_group_cache_system(mut bind_group_cache: ResMut<BindGroupCache>) {
+    bind_group_cache.update();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BindGroupCache;
+    use crate::{
+        render_resource::{binding_types::uniform_buffer_sized, BindGroupEntries},
+        renderer::headless_render_device,
+    };
+    use wgpu::{BufferDescriptor, BufferSize, BufferUsages, ShaderStages};
+
+    #[test]
+    fn reuses_identical_bind_groups_until_idle() {
+        let Some((render_device, _)) = headless_render_device() else {
+            return;
+        };
+        let layout = render_device.create_bind_group_layout(
+            None,
+            &[uniform_buffer_sized(false, BufferSize::new(16)).build(0, ShaderStages::VERTEX)],
+        );
+        let buffer = || {
+            render_device.create_buffer(&BufferDescriptor {
+                label: None,
+                size: 16,
+                usage: BufferUsages::UNIFORM,
+                mapped_at_creation: false,
+            })
+        };
+        let first_buffer = buffer();
+        let second_buffer = buffer();
+
+        let mut cache = BindGroupCache::default();
+        let first = cache.get(
+            &render_device,
+            "first",
+            &layout,
+            &BindGroupEntries::single(first_buffer.as_entire_binding()),
+        );
+
+        // The same resources on the next frame return the cached bind group.
+        cache.update();
+        let reused = cache.get(
+            &render_device,
+            "reused",
+            &layout,
+            &BindGroupEntries::single(first_buffer.as_entire_binding()),
+        );
+        assert_eq!(first.id(), reused.id());
+
+        // Other resources create a new bind group.
+        let other = cache.get(
+            &render_device,
+            "other",
+            &layout,
+            &BindGroupEntries::single(second_buffer.as_entire_binding()),
+        );
+        assert_ne!(first.id(), other.id());
+        assert_eq!(cache.len(), 2);
+
+        // Bind groups that aren't used for three frames are evicted.
+        cache.update();
+        cache.update();
+        assert_eq!(cache.len(), 2);
+        cache.update();
+        assert!(cache.is_empty());
+        let recreated = cache.get(
+            &render_device,
+            "recreated",
+            &layout,
+            &BindGroupEntries::single(first_buffer.as_entire_binding()),
+        );
+        assert_ne!(first.id(), recreated.id());
+    }
+}
@@ -1,5 +1,11 @@
 use crate::{define_atomic_id, render_resource::resource_macros::*};
-use std::ops::Deref;
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+use std::{collections::BTreeMap, num::NonZeroU64, ops::Deref};
+use thiserror::Error;
+use wgpu::{
+    BindGroupLayoutEntry, BindingType, BufferBindingType, SamplerBindingType, ShaderStages,
+    StorageTextureAccess, TextureFormat, TextureSampleType, TextureViewDimension,
+};
 
 define_atomic_id!(BindGroupLayoutId);
 render_resource_wrapper!(ErasedBindGroupLayout, wgpu::BindGroupLayout);
@@ -45,3 +51,246 @@ impl Deref for BindGroupLayout {
         &self.value
     }
 }
+
+#[derive(Error, Debug)]
+pub enum BindGroupLayoutReflectError {
+    #[error(transparent)]
+    Validation(#[from] naga::WithSpan<naga::valid::ValidationError>),
+    #[error(
+        "binding {binding} of group {group} has a type that can't be part of a bind group layout"
+    )]
+    UnsupportedBinding { group: u32, binding: u32 },
+}
+
+/// Reflects the resource bindings declared by a shader module into [`BindGroupLayoutEntry`]s,
+/// grouped by bind group index.
+///
+/// The visibility of each entry is the set of entry point stages that actually use the binding.
+/// The result can be passed to
+/// [`RenderDevice::create_bind_group_layout`](crate::renderer::RenderDevice::create_bind_group_layout)
+/// directly, or compared against a hand-written layout to catch drift between the two.
+///
+/// The module must be fully resolved, so shaders using `#import` or `#ifdef` have to be composed
+/// before they can be reflected. Buffer bindings are reflected without dynamic offsets, and
+/// float textures are assumed to be filterable unless they are multisampled.
+pub fn reflect_bind_group_layout_entries(
+    module: &naga::Module,
+) -> Result<BTreeMap<u32, Vec<BindGroupLayoutEntry>>, BindGroupLayoutReflectError> {
+    let info = Validator::new(ValidationFlags::all(), Capabilities::all()).validate(module)?;
+
+    let mut groups: BTreeMap<u32, Vec<BindGroupLayoutEntry>> = BTreeMap::new();
+    for (handle, variable) in module.global_variables.iter() {
+        let Some(binding) = &variable.binding else {
+            continue;
+        };
+
+        let mut visibility = ShaderStages::NONE;
+        for (index, entry_point) in module.entry_points.iter().enumerate() {
+            if !info.get_entry_point(index)[handle].is_empty() {
+                visibility |= match entry_point.stage {
+                    naga::ShaderStage::Vertex => ShaderStages::VERTEX,
+                    naga::ShaderStage::Fragment => ShaderStages::FRAGMENT,
+                    naga::ShaderStage::Compute => ShaderStages::COMPUTE,
+                };
+            }
+        }
+
+        let unsupported = || BindGroupLayoutReflectError::UnsupportedBinding {
+            group: binding.group,
+            binding: binding.binding,
+        };
+
+        let (mut ty, mut count) = (variable.ty, None);
+        if let naga::TypeInner::BindingArray { base, size } = module.types[ty].inner {
+            let naga::ArraySize::Constant(size) = size else {
+                return Err(unsupported());
+            };
+            ty = base;
+            count = Some(size);
+        }
+
+        let binding_type = match variable.space {
+            naga::AddressSpace::Uniform => BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: NonZeroU64::new(
+                    module.types[ty].inner.size(module.to_ctx()) as u64
+                ),
+            },
+            naga::AddressSpace::Storage { access } => BindingType::Buffer {
+                ty: BufferBindingType::Storage {
+                    read_only: !access.contains(naga::StorageAccess::STORE),
+                },
+                has_dynamic_offset: false,
+                min_binding_size: NonZeroU64::new(
+                    module.types[ty].inner.size(module.to_ctx()) as u64
+                ),
+            },
+            naga::AddressSpace::Handle => match module.types[ty].inner {
+                naga::TypeInner::Sampler { comparison } => BindingType::Sampler(if comparison {
+                    SamplerBindingType::Comparison
+                } else {
+                    SamplerBindingType::Filtering
+                }),
+                naga::TypeInner::Image {
+                    dim,
+                    arrayed,
+                    class,
+                } => {
+                    let view_dimension = match (dim, arrayed) {
+                        (naga::ImageDimension::D1, _) => TextureViewDimension::D1,
+                        (naga::ImageDimension::D2, false) => TextureViewDimension::D2,
+                        (naga::ImageDimension::D2, true) => TextureViewDimension::D2Array,
+                        (naga::ImageDimension::D3, _) => TextureViewDimension::D3,
+                        (naga::ImageDimension::Cube, false) => TextureViewDimension::Cube,
+                        (naga::ImageDimension::Cube, true) => TextureViewDimension::CubeArray,
+                    };
+                    match class {
+                        naga::ImageClass::Sampled { kind, multi } => BindingType::Texture {
+                            sample_type: match kind {
+                                naga::ScalarKind::Float => {
+                                    TextureSampleType::Float { filterable: !multi }
+                                }
+                                naga::ScalarKind::Sint => TextureSampleType::Sint,
+                                naga::ScalarKind::Uint => TextureSampleType::Uint,
+                                _ => return Err(unsupported()),
+                            },
+                            view_dimension,
+                            multisampled: multi,
+                        },
+                        naga::ImageClass::Depth { multi } => BindingType::Texture {
+                            sample_type: TextureSampleType::Depth,
+                            view_dimension,
+                            multisampled: multi,
+                        },
+                        naga::ImageClass::Storage { format, access } => {
+                            BindingType::StorageTexture {
+                                access: match (
+                                    access.contains(naga::StorageAccess::LOAD),
+                                    access.contains(naga::StorageAccess::STORE),
+                                ) {
+                                    (true, true) => StorageTextureAccess::ReadWrite,
+                                    (true, false) => StorageTextureAccess::ReadOnly,
+                                    _ => StorageTextureAccess::WriteOnly,
+                                },
+                                format: storage_texture_format(format).ok_or_else(unsupported)?,
+                                view_dimension,
+                            }
+                        }
+                    }
+                }
+                _ => return Err(unsupported()),
+            },
+            _ => return Err(unsupported()),
+        };
+
+        groups
+            .entry(binding.group)
+            .or_default()
+            .push(BindGroupLayoutEntry {
+                binding: binding.binding,
+                visibility,
+                ty: binding_type,
+                count,
+            });
+    }
+
+    for entries in groups.values_mut() {
+        entries.sort_by_key(|entry| entry.binding);
+    }
+
+    Ok(groups)
+}
+
+/// Maps the texel formats WGSL allows for storage textures to their [`TextureFormat`].
+fn storage_texture_format(format: naga::StorageFormat) -> Option<TextureFormat> {
+    use naga::StorageFormat as S;
+    Some(match format {
+        S::Rgba8Unorm => TextureFormat::Rgba8Unorm,
+        S::Rgba8Snorm => TextureFormat::Rgba8Snorm,
+        S::Rgba8Uint => TextureFormat::Rgba8Uint,
+        S::Rgba8Sint => TextureFormat::Rgba8Sint,
+        S::Bgra8Unorm => TextureFormat::Bgra8Unorm,
+        S::Rgba16Uint => TextureFormat::Rgba16Uint,
+        S::Rgba16Sint => TextureFormat::Rgba16Sint,
+        S::Rgba16Float => TextureFormat::Rgba16Float,
+        S::R32Uint => TextureFormat::R32Uint,
+        S::R32Sint => TextureFormat::R32Sint,
+        S::R32Float => TextureFormat::R32Float,
+        S::Rg32Uint => TextureFormat::Rg32Uint,
+        S::Rg32Sint => TextureFormat::Rg32Sint,
+        S::Rg32Float => TextureFormat::Rg32Float,
+        S::Rgba32Uint => TextureFormat::Rgba32Uint,
+        S::Rgba32Sint => TextureFormat::Rgba32Sint,
+        S::Rgba32Float => TextureFormat::Rgba32Float,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reflect_uniform_texture_and_sampler() {
+        let module = naga::front::wgsl::parse_str(
+            r"
+struct Material {
+    color: vec4<f32>,
+    roughness: f32,
+}
+
+@group(1) @binding(0) var<uniform> material: Material;
+@group(1) @binding(1) var base_color_texture: texture_2d<f32>;
+@group(1) @binding(2) var base_color_sampler: sampler;
+
+@vertex
+fn vertex(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    return vec4(f32(index), 0.0, 0.0, material.roughness);
+}
+
+@fragment
+fn fragment(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    return material.color * textureSample(base_color_texture, base_color_sampler, uv);
+}
+",
+        )
+        .unwrap();
+
+        let groups = reflect_bind_group_layout_entries(&module).unwrap();
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), vec![1]);
+
+        let entries = &groups[&1];
+        assert_eq!(
+            entries,
+            &vec![
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::VERTEX_FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZeroU64::new(32),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ]
+        );
+    }
+}
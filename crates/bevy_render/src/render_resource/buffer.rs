@@ -1,5 +1,11 @@
-use crate::{define_atomic_id, render_resource::resource_macros::render_resource_wrapper};
-use std::ops::{Bound, Deref, RangeBounds};
+use crate::{
+    define_atomic_id, render_resource::resource_macros::render_resource_wrapper,
+    renderer::TrackedAllocation,
+};
+use std::{
+    ops::{Bound, Deref, RangeBounds},
+    sync::Arc,
+};
 
 define_atomic_id!(BufferId);
 render_resource_wrapper!(ErasedBuffer, wgpu::Buffer);
@@ -8,6 +14,8 @@ render_resource_wrapper!(ErasedBuffer, wgpu::Buffer);
 pub struct Buffer {
     id: BufferId,
     value: ErasedBuffer,
+    /// Keeps the allocation recorded in the device's allocation tracker while any clone is alive.
+    _tracking: Option<Arc<TrackedAllocation>>,
 }
 
 impl Buffer {
@@ -33,6 +41,11 @@ impl Buffer {
     pub fn unmap(&self) {
         self.value.unmap();
     }
+
+    pub(crate) fn with_tracking(mut self, allocation: TrackedAllocation) -> Self {
+        self._tracking = Some(Arc::new(allocation));
+        self
+    }
 }
 
 impl From<wgpu::Buffer> for Buffer {
@@ -40,6 +53,7 @@ impl From<wgpu::Buffer> for Buffer {
         Buffer {
             id: BufferId::new(),
             value: ErasedBuffer::new(value),
+            _tracking: None,
         }
     }
 }
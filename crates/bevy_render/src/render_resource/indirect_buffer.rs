@@ -0,0 +1,484 @@
+use crate::{
+    render_phase::TrackedRenderPass,
+    render_resource::Buffer,
+    renderer::{RenderDevice, RenderQueue},
+};
+use std::{future::Future, marker::PhantomData};
+use wgpu::{
+    util::{DrawIndexedIndirectArgs, DrawIndirectArgs},
+    BufferAsyncError, BufferUsages, Features, MapMode,
+};
+
+/// The arguments of a single indirect draw, either [`DrawIndirectArgs`] or
+/// [`DrawIndexedIndirectArgs`].
+pub trait IndirectArgs {
+    /// The size of the arguments in bytes.
+    const SIZE: usize;
+
+    /// Returns the arguments as they are laid out in an indirect buffer.
+    fn as_bytes(&self) -> &[u8];
+
+    /// Reads the arguments from the first [`SIZE`](Self::SIZE) bytes of `bytes`, as laid out in an
+    /// indirect buffer.
+    fn from_bytes(bytes: &[u8]) -> Self;
+
+    #[doc(hidden)]
+    fn multi_draw_indirect<'a>(
+        pass: &mut TrackedRenderPass<'a>,
+        buffer: &'a Buffer,
+        offset: u64,
+        count: u32,
+    );
+
+    #[doc(hidden)]
+    fn multi_draw_indirect_count<'a>(
+        pass: &mut TrackedRenderPass<'a>,
+        buffer: &'a Buffer,
+        count_buffer: &'a Buffer,
+        max_count: u32,
+    );
+
+    #[doc(hidden)]
+    fn draw_indirect<'a>(pass: &mut TrackedRenderPass<'a>, buffer: &'a Buffer, offset: u64);
+}
+
+impl IndirectArgs for DrawIndirectArgs {
+    const SIZE: usize = std::mem::size_of::<DrawIndirectArgs>();
+
+    fn as_bytes(&self) -> &[u8] {
+        DrawIndirectArgs::as_bytes(self)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let [vertex_count, instance_count, first_vertex, first_instance] =
+            bytemuck::pod_read_unaligned::<[u32; 4]>(&bytes[..Self::SIZE]);
+        DrawIndirectArgs {
+            vertex_count,
+            instance_count,
+            first_vertex,
+            first_instance,
+        }
+    }
+
+    fn multi_draw_indirect<'a>(
+        pass: &mut TrackedRenderPass<'a>,
+        buffer: &'a Buffer,
+        offset: u64,
+        count: u32,
+    ) {
+        pass.multi_draw_indirect(buffer, offset, count);
+    }
+
+    fn multi_draw_indirect_count<'a>(
+        pass: &mut TrackedRenderPass<'a>,
+        buffer: &'a Buffer,
+        count_buffer: &'a Buffer,
+        max_count: u32,
+    ) {
+        pass.multi_draw_indirect_count(buffer, 0, count_buffer, 0, max_count);
+    }
+
+    fn draw_indirect<'a>(pass: &mut TrackedRenderPass<'a>, buffer: &'a Buffer, offset: u64) {
+        pass.draw_indirect(buffer, offset);
+    }
+}
+
+impl IndirectArgs for DrawIndexedIndirectArgs {
+    const SIZE: usize = std::mem::size_of::<DrawIndexedIndirectArgs>();
+
+    fn as_bytes(&self) -> &[u8] {
+        DrawIndexedIndirectArgs::as_bytes(self)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let [index_count, instance_count, first_index, base_vertex, first_instance] =
+            bytemuck::pod_read_unaligned::<[u32; 5]>(&bytes[..Self::SIZE]);
+        DrawIndexedIndirectArgs {
+            index_count,
+            instance_count,
+            first_index,
+            base_vertex: base_vertex as i32,
+            first_instance,
+        }
+    }
+
+    fn multi_draw_indirect<'a>(
+        pass: &mut TrackedRenderPass<'a>,
+        buffer: &'a Buffer,
+        offset: u64,
+        count: u32,
+    ) {
+        pass.multi_draw_indexed_indirect(buffer, offset, count);
+    }
+
+    fn multi_draw_indirect_count<'a>(
+        pass: &mut TrackedRenderPass<'a>,
+        buffer: &'a Buffer,
+        count_buffer: &'a Buffer,
+        max_count: u32,
+    ) {
+        pass.multi_draw_indexed_indirect_count(buffer, 0, count_buffer, 0, max_count);
+    }
+
+    fn draw_indirect<'a>(pass: &mut TrackedRenderPass<'a>, buffer: &'a Buffer, offset: u64) {
+        pass.draw_indexed_indirect(buffer, offset);
+    }
+}
+
+/// How an [`IndirectDrawBuffers`] issues its draws, depending on the features of the device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndirectDrawMode {
+    /// A single draw call reads the number of draws from the count buffer, so the GPU can change
+    /// it. Requires [`Features::MULTI_DRAW_INDIRECT_COUNT`].
+    Count,
+    /// A single draw call issues the number of draws known on the CPU.
+    /// Requires [`Features::MULTI_DRAW_INDIRECT`].
+    Multi,
+    /// One indirect draw call is issued per set of arguments.
+    Single,
+}
+
+impl IndirectDrawMode {
+    /// Returns the most capable mode supported by a device with the given `features`.
+    pub fn from_features(features: Features) -> Self {
+        if features.contains(Features::MULTI_DRAW_INDIRECT_COUNT) {
+            IndirectDrawMode::Count
+        } else if features.contains(Features::MULTI_DRAW_INDIRECT) {
+            IndirectDrawMode::Multi
+        } else {
+            IndirectDrawMode::Single
+        }
+    }
+}
+
+/// A buffer of indirect draw arguments, and a count buffer holding the number of draws, for
+/// GPU-driven rendering.
+///
+/// The count buffer is written with the number of pushed arguments, and is also usable as a
+/// storage buffer so that a compute shader can lower the count, for example after culling.
+/// That count is only respected in [`IndirectDrawMode::Count`]. On devices without
+/// [`Features::MULTI_DRAW_INDIRECT_COUNT`], every pushed draw is issued.
+///
+/// Like [`BufferVec`](crate::render_resource::BufferVec), the arguments are stored in system RAM
+/// until [`write_buffers`](IndirectDrawBuffers::write_buffers) is called. Both buffers can be read
+/// back with [`readback_indirect_parameters`], for example to check how many draws survived
+/// culling.
+pub struct IndirectDrawBuffers<A: IndirectArgs> {
+    args: Vec<u8>,
+    args_buffer: Option<Buffer>,
+    count_buffer: Option<Buffer>,
+    capacity: usize,
+    label: Option<String>,
+    marker: PhantomData<A>,
+}
+
+impl<A: IndirectArgs> Default for IndirectDrawBuffers<A> {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<A: IndirectArgs> IndirectDrawBuffers<A> {
+    pub fn new(label: Option<&str>) -> Self {
+        Self {
+            args: Vec::new(),
+            args_buffer: None,
+            count_buffer: None,
+            capacity: 0,
+            label: label.map(str::to_string),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn args_buffer(&self) -> Option<&Buffer> {
+        self.args_buffer.as_ref()
+    }
+
+    #[inline]
+    pub fn count_buffer(&self) -> Option<&Buffer> {
+        self.count_buffer.as_ref()
+    }
+
+    /// The number of draws pushed since the last [`clear`](IndirectDrawBuffers::clear).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.args.len() / A::SIZE
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// Adds a draw and returns its index.
+    pub fn push(&mut self, args: A) -> usize {
+        let index = self.len();
+        self.args.extend_from_slice(args.as_bytes());
+        index
+    }
+
+    pub fn clear(&mut self) {
+        self.args.clear();
+    }
+
+    /// Returns the pushed arguments as they are laid out in the argument buffer.
+    pub fn args_bytes(&self) -> &[u8] {
+        &self.args
+    }
+
+    /// Queues writing the arguments and the draw count to their buffers, creating or growing the
+    /// buffers first if needed.
+    pub fn write_buffers(&mut self, device: &RenderDevice, queue: &RenderQueue) {
+        if self.is_empty() {
+            return;
+        }
+
+        if self.len() > self.capacity {
+            self.capacity = self.len();
+            self.args_buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: self.label.as_deref(),
+                size: self.args.len() as wgpu::BufferAddress,
+                usage: BufferUsages::INDIRECT
+                    | BufferUsages::STORAGE
+                    | BufferUsages::COPY_SRC
+                    | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+        let len = self.len() as u32;
+        let count_buffer = self.count_buffer.get_or_insert_with(|| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: self.label.as_deref(),
+                size: std::mem::size_of::<u32>() as wgpu::BufferAddress,
+                usage: BufferUsages::INDIRECT
+                    | BufferUsages::STORAGE
+                    | BufferUsages::COPY_SRC
+                    | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        });
+
+        queue.write_buffer(count_buffer, 0, &len.to_le_bytes());
+        if let Some(args_buffer) = &self.args_buffer {
+            queue.write_buffer(args_buffer, 0, &self.args);
+        }
+    }
+
+    /// Issues the draws written by the last [`write_buffers`](IndirectDrawBuffers::write_buffers)
+    /// using `mode`, usually [`IndirectDrawMode::from_features`] of the [`RenderDevice`].
+    pub fn draw<'a>(&'a self, pass: &mut TrackedRenderPass<'a>, mode: IndirectDrawMode) {
+        let (Some(args_buffer), Some(count_buffer)) = (&self.args_buffer, &self.count_buffer)
+        else {
+            return;
+        };
+        let count = self.len() as u32;
+
+        match mode {
+            IndirectDrawMode::Count => {
+                A::multi_draw_indirect_count(pass, args_buffer, count_buffer, count);
+            }
+            IndirectDrawMode::Multi => A::multi_draw_indirect(pass, args_buffer, 0, count),
+            IndirectDrawMode::Single => {
+                for offset in (0..self.args.len()).step_by(A::SIZE) {
+                    A::draw_indirect(pass, args_buffer, offset as u64);
+                }
+            }
+        }
+    }
+}
+
+/// Decodes every complete set of indirect arguments in `bytes`.
+pub fn read_indirect_args<A: IndirectArgs>(bytes: &[u8]) -> Vec<A> {
+    bytes.chunks_exact(A::SIZE).map(A::from_bytes).collect()
+}
+
+/// Copies the indirect arguments in `buffer` to a staging buffer and reads them back to the CPU.
+///
+/// `buffer` must have been created with [`BufferUsages::COPY_SRC`], like the argument buffer of
+/// an [`IndirectDrawBuffers`]. The copy is submitted immediately, but the returned future only
+/// resolves once the device has been polled, which happens when the next frame is submitted.
+pub fn readback_indirect_parameters<A: IndirectArgs>(
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+    buffer: &Buffer,
+) -> impl Future<Output = Result<Vec<A>, BufferAsyncError>> {
+    let size = buffer.size() / A::SIZE as u64 * A::SIZE as u64;
+    // wgpu can't map an empty range, so there is nothing to copy if no arguments fit.
+    let readback = (size > 0).then(|| {
+        let staging_buffer = render_device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("indirect_parameters_readback_buffer"),
+            size,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = render_device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("indirect_parameters_readback_encoder"),
+        });
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging_buffer, 0, size);
+        render_queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = async_channel::bounded(1);
+        render_device.map_buffer(&staging_buffer.slice(..), MapMode::Read, move |result| {
+            let _ = sender.try_send(result);
+        });
+        (staging_buffer, receiver)
+    });
+
+    async move {
+        let Some((staging_buffer, receiver)) = readback else {
+            return Ok(Vec::new());
+        };
+        // The callback is dropped without being called if the device is lost.
+        receiver.recv().await.unwrap_or(Err(BufferAsyncError))?;
+        let args = read_indirect_args(&staging_buffer.slice(..).get_mapped_range());
+        staging_buffer.unmap();
+        Ok(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        read_indirect_args, readback_indirect_parameters, IndirectDrawBuffers, IndirectDrawMode,
+    };
+    use crate::renderer::headless_render_device;
+    use wgpu::{
+        util::{BufferInitDescriptor, DrawIndexedIndirectArgs, DrawIndirectArgs},
+        BufferUsages, Features, Maintain,
+    };
+
+    #[test]
+    fn draw_mode_falls_back_without_count_support() {
+        assert_eq!(
+            IndirectDrawMode::from_features(Features::empty()),
+            IndirectDrawMode::Single
+        );
+        assert_eq!(
+            IndirectDrawMode::from_features(Features::MULTI_DRAW_INDIRECT),
+            IndirectDrawMode::Multi
+        );
+        assert_eq!(
+            IndirectDrawMode::from_features(
+                Features::MULTI_DRAW_INDIRECT | Features::MULTI_DRAW_INDIRECT_COUNT
+            ),
+            IndirectDrawMode::Count
+        );
+    }
+
+    #[test]
+    fn args_are_tightly_packed() {
+        let mut buffers = IndirectDrawBuffers::<DrawIndirectArgs>::default();
+        for (i, vertex_count) in [3, 6].into_iter().enumerate() {
+            let index = buffers.push(DrawIndirectArgs {
+                vertex_count,
+                instance_count: 1,
+                first_vertex: 0,
+                first_instance: i as u32,
+            });
+            assert_eq!(index, i);
+        }
+        assert_eq!(buffers.len(), 2);
+        let words: Vec<u32> = buffers
+            .args_bytes()
+            .chunks_exact(4)
+            .map(|bytes| u32::from_ne_bytes(bytes.try_into().unwrap()))
+            .collect();
+        assert_eq!(words, [3, 1, 0, 0, 6, 1, 0, 1]);
+
+        let mut indexed = IndirectDrawBuffers::<DrawIndexedIndirectArgs>::default();
+        indexed.push(DrawIndexedIndirectArgs {
+            index_count: 36,
+            instance_count: 2,
+            first_index: 0,
+            base_vertex: 0,
+            first_instance: 0,
+        });
+        assert_eq!(indexed.args_bytes().len(), 20);
+        assert_eq!(indexed.len(), 1);
+    }
+
+    #[test]
+    fn args_are_read_back_from_their_bytes() {
+        let mut buffers = IndirectDrawBuffers::<DrawIndexedIndirectArgs>::default();
+        let args = [(36, 2, 0, -4, 0), (6, 1, 36, 100, 2)];
+        for (index_count, instance_count, first_index, base_vertex, first_instance) in args {
+            buffers.push(DrawIndexedIndirectArgs {
+                index_count,
+                instance_count,
+                first_index,
+                base_vertex,
+                first_instance,
+            });
+        }
+
+        let mut bytes = buffers.args_bytes().to_vec();
+        // A trailing partial set of arguments is ignored.
+        bytes.extend_from_slice(&[0; 8]);
+        let read: Vec<_> = read_indirect_args::<DrawIndexedIndirectArgs>(&bytes)
+            .into_iter()
+            .map(|args| {
+                (
+                    args.index_count,
+                    args.instance_count,
+                    args.first_index,
+                    args.base_vertex,
+                    args.first_instance,
+                )
+            })
+            .collect();
+        assert_eq!(read, args);
+
+        let draw = DrawIndirectArgs {
+            vertex_count: 3,
+            instance_count: 7,
+            first_vertex: 9,
+            first_instance: 1,
+        };
+        let read = read_indirect_args::<DrawIndirectArgs>(draw.as_bytes());
+        assert_eq!(read.len(), 1);
+        assert_eq!(
+            (
+                read[0].vertex_count,
+                read[0].instance_count,
+                read[0].first_vertex
+            ),
+            (3, 7, 9)
+        );
+    }
+
+    #[test]
+    fn args_are_read_back_from_the_gpu() {
+        let Some((render_device, render_queue)) = headless_render_device() else {
+            return;
+        };
+        let draw = DrawIndirectArgs {
+            vertex_count: 3,
+            instance_count: 7,
+            first_vertex: 9,
+            first_instance: 1,
+        };
+        let readback = |contents: &[u8]| {
+            let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: None,
+                contents,
+                usage: BufferUsages::COPY_SRC,
+            });
+            let read = readback_indirect_parameters::<DrawIndirectArgs>(
+                &render_device,
+                &render_queue,
+                &buffer,
+            );
+            render_device.poll(Maintain::Wait);
+            futures_lite::future::block_on(read).unwrap()
+        };
+
+        let read = readback(draw.as_bytes());
+        assert_eq!(read.len(), 1);
+        assert_eq!((read[0].vertex_count, read[0].first_instance), (3, 1));
+
+        // The buffer is too small to hold a set of arguments.
+        assert!(readback(&[0; 8]).is_empty());
+    }
+}
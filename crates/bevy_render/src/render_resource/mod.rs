@@ -1,14 +1,17 @@
 mod batched_uniform_buffer;
 mod bind_group;
+mod bind_group_cache;
 mod bind_group_entries;
 mod bind_group_layout;
 mod bind_group_layout_entries;
 mod buffer;
 mod buffer_vec;
 mod gpu_array_buffer;
+mod indirect_buffer;
 mod pipeline;
 mod pipeline_cache;
 mod pipeline_specializer;
+mod render_pass;
 pub mod resource_macros;
 mod shader;
 mod storage_buffer;
@@ -16,15 +19,18 @@ mod texture;
 mod uniform_buffer;
 
 pub use bind_group::*;
+pub use bind_group_cache::*;
 pub use bind_group_entries::*;
 pub use bind_group_layout::*;
 pub use bind_group_layout_entries::*;
 pub use buffer::*;
 pub use buffer_vec::*;
 pub use gpu_array_buffer::*;
+pub use indirect_buffer::*;
 pub use pipeline::*;
 pub use pipeline_cache::*;
 pub use pipeline_specializer::*;
+pub use render_pass::*;
 pub use shader::*;
 pub use storage_buffer::*;
 pub use texture::*;
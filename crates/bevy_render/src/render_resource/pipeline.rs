@@ -4,7 +4,10 @@ use crate::{
     render_resource::{resource_macros::render_resource_wrapper, BindGroupLayout, Shader},
 };
 use bevy_asset::Handle;
+use bevy_math::{IVec2, IVec3, IVec4, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
+pub use bevy_render_macros::AsVertexBufferLayout;
 use std::{borrow::Cow, ops::Deref};
+use thiserror::Error;
 use wgpu::{
     BufferAddress, ColorTargetState, DepthStencilState, MultisampleState, PrimitiveState,
     PushConstantRange, VertexAttribute, VertexFormat, VertexStepMode,
@@ -160,6 +163,169 @@ impl VertexBufferLayout {
     }
 }
 
+/// Builds a densely packed [`VertexBufferLayout`] from attributes with explicit shader locations.
+///
+/// Offsets and the `array_stride` are computed from the [`VertexFormat`]s in the order the
+/// attributes are added, so only the shader locations need to match the vertex shader.
+///
+/// ```
+/// # use bevy_render::render_resource::{VertexBufferLayoutBuilder, VertexFormat, VertexStepMode};
+/// let layout = VertexBufferLayoutBuilder::new(VertexStepMode::Vertex)
+///     .attribute(0, VertexFormat::Float32x3)
+///     .attribute(2, VertexFormat::Float32x2)
+///     .build()
+///     .unwrap();
+/// assert_eq!(layout.array_stride, 20);
+/// ```
+#[derive(Clone, Debug)]
+pub struct VertexBufferLayoutBuilder {
+    step_mode: VertexStepMode,
+    attributes: Vec<(u32, VertexFormat)>,
+}
+
+impl VertexBufferLayoutBuilder {
+    /// Creates a builder for a layout without attributes.
+    pub fn new(step_mode: VertexStepMode) -> Self {
+        Self {
+            step_mode,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Adds an attribute of the given `format` at `shader_location`, placed after the attributes
+    /// added before it.
+    pub fn attribute(mut self, shader_location: u32, format: VertexFormat) -> Self {
+        self.attributes.push((shader_location, format));
+        self
+    }
+
+    /// Builds the [`VertexBufferLayout`], or returns an error if two attributes share a shader
+    /// location.
+    pub fn build(self) -> Result<VertexBufferLayout, VertexBufferLayoutError> {
+        for (index, &(shader_location, second)) in self.attributes.iter().enumerate() {
+            if let Some(&(_, first)) = self.attributes[..index]
+                .iter()
+                .find(|(location, _)| *location == shader_location)
+            {
+                return Err(VertexBufferLayoutError::DuplicateShaderLocation {
+                    shader_location,
+                    first,
+                    second,
+                });
+            }
+        }
+
+        Ok(located_vertex_buffer_layout(
+            self.step_mode,
+            &self.attributes,
+        ))
+    }
+}
+
+/// Packs `attributes` with [`VertexBufferLayout::from_vertex_formats`], keeping their shader
+/// locations instead of numbering them in order.
+fn located_vertex_buffer_layout(
+    step_mode: VertexStepMode,
+    attributes: &[(u32, VertexFormat)],
+) -> VertexBufferLayout {
+    let mut layout = VertexBufferLayout::from_vertex_formats(
+        step_mode,
+        attributes.iter().map(|&(_, format)| format),
+    );
+    for (attribute, &(shader_location, _)) in layout.attributes.iter_mut().zip(attributes) {
+        attribute.shader_location = shader_location;
+    }
+    layout
+}
+
+/// An error returned by [`VertexBufferLayoutBuilder::build`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum VertexBufferLayoutError {
+    #[error(
+        "shader location {shader_location} is used by both a {first:?} and a {second:?} attribute"
+    )]
+    DuplicateShaderLocation {
+        shader_location: u32,
+        first: VertexFormat,
+        second: VertexFormat,
+    },
+}
+
+/// A type that can be used as a single vertex attribute.
+pub trait VertexAttributeType {
+    /// The [`VertexFormat`] of this type in a vertex buffer.
+    const FORMAT: VertexFormat;
+}
+
+macro_rules! impl_vertex_attribute_type {
+    ($($ty:ty => $format:ident),* $(,)?) => {
+        $(
+            impl VertexAttributeType for $ty {
+                const FORMAT: VertexFormat = VertexFormat::$format;
+            }
+        )*
+    };
+}
+
+impl_vertex_attribute_type!(
+    f32 => Float32,
+    u32 => Uint32,
+    i32 => Sint32,
+    [f32; 2] => Float32x2,
+    [f32; 3] => Float32x3,
+    [f32; 4] => Float32x4,
+    [u32; 2] => Uint32x2,
+    [u32; 3] => Uint32x3,
+    [u32; 4] => Uint32x4,
+    [i32; 2] => Sint32x2,
+    [i32; 3] => Sint32x3,
+    [i32; 4] => Sint32x4,
+    Vec2 => Float32x2,
+    Vec3 => Float32x3,
+    Vec4 => Float32x4,
+    UVec2 => Uint32x2,
+    UVec3 => Uint32x3,
+    UVec4 => Uint32x4,
+    IVec2 => Sint32x2,
+    IVec3 => Sint32x3,
+    IVec4 => Sint32x4,
+);
+
+/// A vertex type whose [`VertexBufferLayout`] is derived from its fields.
+///
+/// This trait can be derived with `#[derive(AsVertexBufferLayout)]`, which keeps the layout in
+/// sync with the struct instead of spelling out each [`VertexAttribute`] by hand:
+///
+/// ```ignore (the derive refers to this crate as bevy_render, which only resolves downstream)
+/// # use bevy_render::render_resource::{AsVertexBufferLayout, VertexStepMode};
+/// # use bevy_math::{Vec2, Vec3};
+/// #[derive(AsVertexBufferLayout)]
+/// #[repr(C)]
+/// struct Vertex {
+///     #[location(0)]
+///     position: Vec3,
+///     #[location(2)]
+///     uv: Vec2,
+/// }
+///
+/// let layout = Vertex::vertex_buffer_layout(VertexStepMode::Vertex);
+/// assert_eq!(layout.array_stride, 20);
+/// ```
+///
+/// Attributes are densely packed in field order, which matches the memory layout of a
+/// `#[repr(C)]` struct of 4-byte scalars and vectors. The derive rejects structs that aren't
+/// `#[repr(C)]`, and fails to compile if the size of the struct differs from the sum of the
+/// attribute sizes because of padding.
+pub trait AsVertexBufferLayout {
+    /// The shader location and format of each attribute, in field order.
+    const ATTRIBUTES: &'static [(u32, VertexFormat)];
+
+    /// Creates the [`VertexBufferLayout`] of this vertex type.
+    fn vertex_buffer_layout(step_mode: VertexStepMode) -> VertexBufferLayout {
+        located_vertex_buffer_layout(step_mode, Self::ATTRIBUTES)
+    }
+}
+
 /// Describes the fragment process in a render pipeline.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct FragmentState {
@@ -186,3 +352,78 @@ pub struct ComputePipelineDescriptor {
     /// function with this name in the shader.
     pub entry_point: Cow<'static, str>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AsVertexBufferLayout, VertexAttribute, VertexBufferLayoutBuilder, VertexBufferLayoutError,
+        VertexFormat, VertexStepMode,
+    };
+    use bevy_math::{Vec2, Vec3};
+
+    #[test]
+    fn derived_vertex_buffer_layout() {
+        #[derive(AsVertexBufferLayout)]
+        #[repr(C)]
+        #[allow(dead_code)]
+        struct Vertex {
+            position: Vec3,
+            #[location(3)]
+            uv: Vec2,
+        }
+
+        let layout = Vertex::vertex_buffer_layout(VertexStepMode::Vertex);
+        assert_eq!(layout.array_stride, std::mem::size_of::<Vertex>() as u64);
+        assert_eq!(layout.step_mode, VertexStepMode::Vertex);
+        assert_eq!(
+            layout.attributes,
+            vec![
+                VertexAttribute {
+                    format: VertexFormat::Float32x3,
+                    offset: 0,
+                    shader_location: 0,
+                },
+                VertexAttribute {
+                    format: VertexFormat::Float32x2,
+                    offset: 12,
+                    shader_location: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn built_vertex_buffer_layout() {
+        // Position, normal, tangent and UV.
+        let layout = VertexBufferLayoutBuilder::new(VertexStepMode::Vertex)
+            .attribute(0, VertexFormat::Float32x3)
+            .attribute(1, VertexFormat::Float32x3)
+            .attribute(4, VertexFormat::Float32x4)
+            .attribute(2, VertexFormat::Float32x2)
+            .build()
+            .unwrap();
+        assert_eq!(layout.array_stride, 48);
+        assert_eq!(
+            layout
+                .attributes
+                .iter()
+                .map(|attribute| (attribute.shader_location, attribute.offset))
+                .collect::<Vec<_>>(),
+            [(0, 0), (1, 12), (4, 24), (2, 40)]
+        );
+
+        let error = VertexBufferLayoutBuilder::new(VertexStepMode::Instance)
+            .attribute(0, VertexFormat::Float32x3)
+            .attribute(0, VertexFormat::Float32x2)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            VertexBufferLayoutError::DuplicateShaderLocation {
+                shader_location: 0,
+                first: VertexFormat::Float32x3,
+                second: VertexFormat::Float32x2,
+            }
+        );
+    }
+}
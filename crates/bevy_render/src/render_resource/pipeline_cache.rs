@@ -9,6 +9,7 @@ use bevy_utils::{
     Entry, HashMap, HashSet,
 };
 use naga::valid::Capabilities;
+use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
     future::Future,
@@ -78,6 +79,15 @@ impl CachedComputePipelineId {
     }
 }
 
+/// Index of a cached render or compute pipeline in a [`PipelineCache`].
+///
+/// Returned by [`PipelineCache::queue_warmup`].
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum AnyPipelineId {
+    Render(CachedRenderPipelineId),
+    Compute(CachedComputePipelineId),
+}
+
 pub struct CachedPipeline {
     pub descriptor: PipelineDescriptor,
     pub state: CachedPipelineState,
@@ -137,7 +147,7 @@ struct ShaderCache {
     composer: naga_oil::compose::Composer,
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+#[derive(Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum ShaderDefVal {
     Bool(String, bool),
     Int(String, i32),
@@ -657,19 +667,89 @@ impl PipelineCache {
         id
     }
 
+    /// Insert render and compute pipelines into the cache ahead of their first use, and queue
+    /// their creation.
+    ///
+    /// This is meant to be called during loading screens, so that pipelines known to be needed
+    /// later are compiled by [`process_pipeline_queue_system`] before they're drawn with, instead
+    /// of stuttering on first use. Like with [`queue_render_pipeline()`], there is no
+    /// deduplication: the returned IDs have to be kept and used instead of queueing the same
+    /// descriptors again. [`pending_count()`] can be used to track progress.
+    ///
+    /// [`process_pipeline_queue_system`]: PipelineCache::process_pipeline_queue_system
+    /// [`queue_render_pipeline()`]: PipelineCache::queue_render_pipeline
+    /// [`pending_count()`]: PipelineCache::pending_count
+    pub fn queue_warmup(
+        &self,
+        descriptors: impl IntoIterator<Item = PipelineDescriptor>,
+    ) -> Vec<AnyPipelineId> {
+        descriptors
+            .into_iter()
+            .map(|descriptor| match descriptor {
+                PipelineDescriptor::RenderPipelineDescriptor(descriptor) => {
+                    AnyPipelineId::Render(self.queue_render_pipeline(*descriptor))
+                }
+                PipelineDescriptor::ComputePipelineDescriptor(descriptor) => {
+                    AnyPipelineId::Compute(self.queue_compute_pipeline(*descriptor))
+                }
+            })
+            .collect()
+    }
+
+    /// The number of pipelines that are queued or being created.
+    ///
+    /// Pipelines waiting for a shader that isn't loaded yet are counted, while pipelines that
+    /// failed to be created are not. This reaches zero once every queued pipeline was processed,
+    /// whether pipelines are compiled asynchronously or not.
+    pub fn pending_count(&self) -> usize {
+        let new_pipelines = self
+            .new_pipelines
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Pipelines created synchronously stay in `waiting_pipelines` until the next
+        // `process_queue`, and so do pipelines that failed for good until their error is logged.
+        let waiting_pipelines = self
+            .waiting_pipelines
+            .iter()
+            .filter(|&&id| match &self.pipelines[id].state {
+                CachedPipelineState::Queued | CachedPipelineState::Creating(_) => true,
+                CachedPipelineState::Err(err) => matches!(
+                    err,
+                    PipelineCacheError::ShaderNotLoaded(_)
+                        | PipelineCacheError::ShaderImportNotYetAvailable
+                ),
+                CachedPipelineState::Ok(_) => false,
+            })
+            .count();
+        new_pipelines.len() + waiting_pipelines
+    }
+
+    /// Discards the compiled modules of a shader and of every shader importing it, directly or
+    /// through other imports, and queues the pipelines using them to be recreated.
+    ///
+    /// This already happens when a [`Shader`] asset is modified or removed, so it's only needed
+    /// to force a rebuild when something other than the shader asset itself changed.
+    pub fn invalidate_shader(&mut self, shader: impl Into<AssetId<Shader>>) {
+        let pipelines_to_queue = self.shader_cache.lock().unwrap().clear(shader.into());
+        self.queue_pipelines(pipelines_to_queue);
+    }
+
     fn set_shader(&mut self, id: AssetId<Shader>, shader: &Shader) {
-        let mut shader_cache = self.shader_cache.lock().unwrap();
-        let pipelines_to_queue = shader_cache.set_shader(id, shader.clone());
-        for cached_pipeline in pipelines_to_queue {
-            self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
-            self.waiting_pipelines.insert(cached_pipeline);
-        }
+        let pipelines_to_queue = self
+            .shader_cache
+            .lock()
+            .unwrap()
+            .set_shader(id, shader.clone());
+        self.queue_pipelines(pipelines_to_queue);
     }
 
     fn remove_shader(&mut self, shader: AssetId<Shader>) {
-        let mut shader_cache = self.shader_cache.lock().unwrap();
-        let pipelines_to_queue = shader_cache.remove(shader);
-        for cached_pipeline in pipelines_to_queue {
+        let pipelines_to_queue = self.shader_cache.lock().unwrap().remove(shader);
+        self.queue_pipelines(pipelines_to_queue);
+    }
+
+    fn queue_pipelines(&mut self, pipelines: Vec<CachedPipelineId>) {
+        for cached_pipeline in pipelines {
             self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
             self.waiting_pipelines.insert(cached_pipeline);
         }
@@ -881,7 +961,9 @@ impl PipelineCache {
             CachedPipelineState::Err(err) => match err {
                 // Retry
                 PipelineCacheError::ShaderNotLoaded(_)
-                | PipelineCacheError::ShaderImportNotYetAvailable => {}
+                | PipelineCacheError::ShaderImportNotYetAvailable => {
+                    cached_pipeline.state = CachedPipelineState::Queued;
+                }
 
                 // Shader could not be processed ... retrying won't help
                 PipelineCacheError::ProcessShaderError(err) => {
@@ -979,3 +1061,111 @@ pub enum PipelineCacheError {
     #[error("Could not create shader module: {0}")]
     CreateShaderModule(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AnyPipelineId, CachedPipelineState, PipelineCache, PipelineDescriptor};
+    use crate::{
+        render_resource::{ComputePipelineDescriptor, Shader},
+        renderer::headless_render_device,
+    };
+    use bevy_asset::Handle;
+
+    const SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(5237164387456123987);
+    const IMPORTED_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(8412370952385021654);
+
+    fn compute_descriptor(shader: Handle<Shader>) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: None,
+            layout: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            shader,
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+        }
+    }
+
+    #[test]
+    fn pending_count_drains_after_warmup() {
+        let Some((render_device, _)) = headless_render_device() else {
+            return;
+        };
+        let mut cache = PipelineCache::new(render_device, true);
+
+        let ids = cache.queue_warmup((0..3).map(|_| {
+            PipelineDescriptor::ComputePipelineDescriptor(Box::new(compute_descriptor(
+                SHADER_HANDLE,
+            )))
+        }));
+        assert_eq!(ids.len(), 3);
+        assert_eq!(cache.pending_count(), 3);
+
+        // Pipelines waiting for their shader stay pending.
+        cache.process_queue();
+        assert_eq!(cache.pending_count(), 3);
+
+        cache.set_shader(
+            SHADER_HANDLE.id(),
+            &Shader::from_wgsl("@compute @workgroup_size(1) fn main() {}", "warmup.wgsl"),
+        );
+        // The pipelines are queued again once the shader is loaded, and created after that.
+        cache.process_queue();
+        assert_eq!(cache.pending_count(), 3);
+        cache.process_queue();
+        assert_eq!(cache.pending_count(), 0);
+        for id in ids {
+            let AnyPipelineId::Compute(id) = id else {
+                panic!("expected a compute pipeline");
+            };
+            assert!(matches!(
+                cache.get_compute_pipeline_state(id),
+                CachedPipelineState::Ok(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn invalidating_an_imported_shader_requeues_importing_pipelines() {
+        let Some((render_device, _)) = headless_render_device() else {
+            return;
+        };
+        let mut cache = PipelineCache::new(render_device, true);
+        cache.set_shader(
+            IMPORTED_SHADER_HANDLE.id(),
+            &Shader::from_wgsl(
+                "#define_import_path test::imported\nfn value() -> f32 { return 1.0; }",
+                "imported.wgsl",
+            ),
+        );
+        cache.set_shader(
+            SHADER_HANDLE.id(),
+            &Shader::from_wgsl(
+                "#import test::imported::value\n\
+                 @compute @workgroup_size(1) fn main() { let x = value(); }",
+                "main.wgsl",
+            ),
+        );
+        let id = cache.queue_compute_pipeline(compute_descriptor(SHADER_HANDLE));
+        cache.process_queue();
+        assert!(matches!(
+            cache.get_compute_pipeline_state(id),
+            CachedPipelineState::Ok(_)
+        ));
+        assert_eq!(cache.pending_count(), 0);
+
+        // The pipeline only uses the imported shader through its import.
+        cache.invalidate_shader(IMPORTED_SHADER_HANDLE);
+        assert!(matches!(
+            cache.get_compute_pipeline_state(id),
+            CachedPipelineState::Queued
+        ));
+        assert_eq!(cache.pending_count(), 1);
+
+        cache.process_queue();
+        assert!(matches!(
+            cache.get_compute_pipeline_state(id),
+            CachedPipelineState::Ok(_)
+        ));
+        assert_eq!(cache.pending_count(), 0);
+    }
+}
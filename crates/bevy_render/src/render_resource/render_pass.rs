@@ -0,0 +1,167 @@
+use wgpu::{
+    BlendState, ColorTargetState, ColorWrites, RenderPassColorAttachment,
+    RenderPassDepthStencilAttachment, RenderPassDescriptor, TextureFormat,
+};
+
+/// Collects the attachments of a render pass along with their [`TextureFormat`]s, so that the
+/// [`ColorTargetState`]s of the pipelines used in the pass can be derived from the same list.
+///
+/// Attachments keep the order they are added in, which is also the order of the color targets
+/// returned by [`RenderPassBuilder::color_target_states`].
+#[derive(Default)]
+pub struct RenderPassBuilder<'a> {
+    label: Option<&'a str>,
+    color_attachments: Vec<Option<RenderPassColorAttachment<'a>>>,
+    color_formats: Vec<Option<TextureFormat>>,
+    depth_stencil_attachment: Option<RenderPassDepthStencilAttachment<'a>>,
+    depth_stencil_format: Option<TextureFormat>,
+}
+
+impl<'a> RenderPassBuilder<'a> {
+    /// Creates a builder for a render pass without attachments.
+    pub fn new(label: impl Into<Option<&'a str>>) -> Self {
+        Self {
+            label: label.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a color attachment whose texture view has the given `format`.
+    pub fn color_attachment(
+        mut self,
+        attachment: RenderPassColorAttachment<'a>,
+        format: TextureFormat,
+    ) -> Self {
+        self.color_attachments.push(Some(attachment));
+        self.color_formats.push(Some(format));
+        self
+    }
+
+    /// Leaves the next color attachment slot empty, so that fragment shader outputs at its
+    /// location are discarded.
+    pub fn unused_color_attachment(mut self) -> Self {
+        self.color_attachments.push(None);
+        self.color_formats.push(None);
+        self
+    }
+
+    /// Sets the depth stencil attachment, whose texture view has the given `format`.
+    pub fn depth_stencil_attachment(
+        mut self,
+        attachment: RenderPassDepthStencilAttachment<'a>,
+        format: TextureFormat,
+    ) -> Self {
+        self.depth_stencil_attachment = Some(attachment);
+        self.depth_stencil_format = Some(format);
+        self
+    }
+
+    /// The formats of the color attachments, with `None` for unused slots.
+    pub fn color_formats(&self) -> &[Option<TextureFormat>] {
+        &self.color_formats
+    }
+
+    /// The format of the depth stencil attachment, if there is one.
+    pub fn depth_stencil_format(&self) -> Option<TextureFormat> {
+        self.depth_stencil_format
+    }
+
+    /// Returns a [`ColorTargetState`] for each color attachment, matching its format, to use in
+    /// the [`FragmentState`](super::FragmentState) of the pipelines drawing in this pass.
+    pub fn color_target_states(
+        &self,
+        blend: Option<BlendState>,
+        write_mask: ColorWrites,
+    ) -> Vec<Option<ColorTargetState>> {
+        self.color_formats
+            .iter()
+            .map(|format| {
+                format.map(|format| ColorTargetState {
+                    format,
+                    blend,
+                    write_mask,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the descriptor to begin the render pass with.
+    pub fn descriptor(&self) -> RenderPassDescriptor<'a, '_> {
+        RenderPassDescriptor {
+            label: self.label,
+            color_attachments: &self.color_attachments,
+            depth_stencil_attachment: self.depth_stencil_attachment.clone(),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderPassBuilder;
+    use crate::renderer::headless_render_device;
+    use wgpu::{
+        BlendState, ColorTargetState, ColorWrites, Extent3d, Operations, RenderPassColorAttachment,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    };
+
+    #[test]
+    fn color_target_states_match_the_attachments() {
+        let Some((render_device, _)) = headless_render_device() else {
+            return;
+        };
+        let view = |format| {
+            render_device
+                .create_texture(&TextureDescriptor {
+                    label: None,
+                    size: Extent3d {
+                        width: 4,
+                        height: 4,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format,
+                    usage: TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                })
+                .create_view(&Default::default())
+        };
+        let color = view(TextureFormat::Rgba8UnormSrgb);
+        let normals = view(TextureFormat::Rgba16Float);
+        let attachment = |view| RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: Operations::default(),
+        };
+
+        let builder = RenderPassBuilder::new("two_targets")
+            .color_attachment(attachment(&color), TextureFormat::Rgba8UnormSrgb)
+            .unused_color_attachment()
+            .color_attachment(attachment(&normals), TextureFormat::Rgba16Float);
+
+        let target = |format| {
+            Some(ColorTargetState {
+                format,
+                blend: Some(BlendState::ALPHA_BLENDING),
+                write_mask: ColorWrites::COLOR,
+            })
+        };
+        assert_eq!(
+            builder.color_target_states(Some(BlendState::ALPHA_BLENDING), ColorWrites::COLOR),
+            [
+                target(TextureFormat::Rgba8UnormSrgb),
+                None,
+                target(TextureFormat::Rgba16Float),
+            ]
+        );
+        assert_eq!(builder.depth_stencil_format(), None);
+
+        let descriptor = builder.descriptor();
+        assert_eq!(descriptor.label, Some("two_targets"));
+        assert_eq!(descriptor.color_attachments.len(), 3);
+        assert!(descriptor.color_attachments[1].is_none());
+    }
+}
@@ -4,6 +4,7 @@ use bevy_asset::{io::Reader, Asset, AssetLoader, AssetPath, Handle, LoadContext}
 use bevy_reflect::TypePath;
 use bevy_utils::{tracing::error, BoxedFuture};
 use futures_lite::AsyncReadExt;
+use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, marker::Copy};
 use thiserror::Error;
 
@@ -21,6 +22,9 @@ pub enum ShaderReflectError {
     SpirVParse(#[from] naga::front::spv::Error),
     #[error(transparent)]
     Validation(#[from] naga::WithSpan<naga::valid::ValidationError>),
+    #[cfg(not(feature = "shader_format_spirv"))]
+    #[error("SPIR-V shaders can only be reflected with the `shader_format_spirv` feature")]
+    SpirVNotSupported,
 }
 /// A shader, as defined by its [`ShaderSource`](wgpu::ShaderSource) and [`ShaderStage`](naga::ShaderStage)
 /// This is an "unprocessed" shader. It can contain preprocessor directives.
@@ -93,6 +97,17 @@ impl Shader {
         }
     }
 
+    /// Adds `shader_defs` to the shader defs this shader is always compiled with, on top of the
+    /// ones set by the pipelines using it.
+    ///
+    /// They are applied by the preprocessor, so they select the `#ifdef`/`#else` branches that
+    /// are compiled.
+    #[must_use]
+    pub fn with_shader_defs(mut self, shader_defs: impl IntoIterator<Item = ShaderDefVal>) -> Self {
+        self.shader_defs.extend(shader_defs);
+        self
+    }
+
     pub fn from_glsl(
         source: impl Into<Cow<'static, str>>,
         stage: naga::ShaderStage,
@@ -143,6 +158,31 @@ impl Shader {
     pub fn imports(&self) -> impl ExactSizeIterator<Item = &ShaderImport> {
         self.imports.iter()
     }
+
+    /// Returns the names of the entry points declared in this shader, along with their stage.
+    ///
+    /// The source is parsed as is, so this fails for shaders using preprocessor directives such
+    /// as `#import` or `#ifdef`, which are only resolved when a pipeline using the shader is
+    /// created.
+    pub fn entry_points(&self) -> Result<Vec<(naga::ShaderStage, String)>, ShaderReflectError> {
+        let module = match &self.source {
+            Source::Wgsl(source) => naga::front::wgsl::parse_str(source)?,
+            // GLSL shaders are for a single stage, and their entry point is always `main`.
+            Source::Glsl(_, stage) => return Ok(vec![(*stage, "main".to_string())]),
+            #[cfg(feature = "shader_format_spirv")]
+            Source::SpirV(source) => {
+                naga::front::spv::parse_u8_slice(source, &naga::front::spv::Options::default())?
+            }
+            #[cfg(not(feature = "shader_format_spirv"))]
+            Source::SpirV(_) => return Err(ShaderReflectError::SpirVNotSupported),
+        };
+
+        Ok(module
+            .entry_points
+            .into_iter()
+            .map(|entry_point| (entry_point.stage, entry_point.name))
+            .collect())
+    }
 }
 
 impl<'a> From<&'a Shader> for naga_oil::compose::ComposableModuleDescriptor<'a> {
@@ -246,6 +286,13 @@ impl From<&Source> for naga_oil::compose::ShaderType {
 #[derive(Default)]
 pub struct ShaderLoader;
 
+/// Settings for loading a [`Shader`] with the [`ShaderLoader`].
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct ShaderLoaderSettings {
+    /// Shader defs added to the loaded shader. See [`Shader::with_shader_defs`].
+    pub shader_defs: Vec<ShaderDefVal>,
+}
+
 #[non_exhaustive]
 #[derive(Debug, Error)]
 pub enum ShaderLoaderError {
@@ -257,12 +304,12 @@ pub enum ShaderLoaderError {
 
 impl AssetLoader for ShaderLoader {
     type Asset = Shader;
-    type Settings = ();
+    type Settings = ShaderLoaderSettings;
     type Error = ShaderLoaderError;
     fn load<'a>(
         &'a self,
         reader: &'a mut Reader,
-        _settings: &'a Self::Settings,
+        settings: &'a Self::Settings,
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<Shader, Self::Error>> {
         Box::pin(async move {
@@ -286,7 +333,8 @@ impl AssetLoader for ShaderLoader {
                     Shader::from_glsl(String::from_utf8(bytes)?, naga::ShaderStage::Compute, path)
                 }
                 _ => panic!("unhandled extension: {ext}"),
-            };
+            }
+            .with_shader_defs(settings.shader_defs.iter().cloned());
 
             // collect and store file dependencies
             for import in &shader.imports {
@@ -345,3 +393,75 @@ impl From<&'static str> for ShaderRef {
         Self::Path(AssetPath::from(path))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Shader;
+    use naga::ShaderStage;
+    use naga_oil::compose::{ComposableModuleDescriptor, Composer, NagaModuleDescriptor};
+
+    #[test]
+    fn entry_points_are_enumerated_with_their_stage() {
+        let shader = Shader::from_wgsl(
+            r"
+@vertex
+fn vertex_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(f32(index), 0.0, 0.0, 1.0);
+}
+
+@fragment
+fn fragment_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+
+fn helper() {}
+",
+            "entry_points.wgsl",
+        );
+
+        assert_eq!(
+            shader.entry_points().unwrap(),
+            [
+                (ShaderStage::Vertex, "vertex_main".to_string()),
+                (ShaderStage::Fragment, "fragment_main".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn shader_defs_select_preprocessor_branches() {
+        let source = r"
+#ifdef FANCY
+@fragment
+fn fancy_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+#else
+@fragment
+fn plain_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0);
+}
+#endif
+";
+        let entry_points = |shader: &Shader| {
+            let ComposableModuleDescriptor { shader_defs, .. } = shader.into();
+            let module = Composer::default()
+                .make_naga_module(NagaModuleDescriptor {
+                    shader_defs,
+                    ..shader.into()
+                })
+                .unwrap();
+            module
+                .entry_points
+                .into_iter()
+                .map(|entry_point| entry_point.name)
+                .collect::<Vec<_>>()
+        };
+
+        let plain = Shader::from_wgsl(source, "defines.wgsl");
+        assert_eq!(entry_points(&plain), ["plain_main"]);
+
+        let fancy = Shader::from_wgsl(source, "defines.wgsl").with_shader_defs(["FANCY".into()]);
+        assert_eq!(entry_points(&fancy), ["fancy_main"]);
+    }
+}
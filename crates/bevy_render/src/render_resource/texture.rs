@@ -1,5 +1,5 @@
-use crate::define_atomic_id;
-use std::ops::Deref;
+use crate::{define_atomic_id, renderer::TrackedAllocation};
+use std::{ops::Deref, sync::Arc};
 
 use crate::render_resource::resource_macros::*;
 
@@ -14,6 +14,8 @@ render_resource_wrapper!(ErasedTexture, wgpu::Texture);
 pub struct Texture {
     id: TextureId,
     value: ErasedTexture,
+    /// Keeps the allocation recorded in the device's allocation tracker while any clone is alive.
+    _tracking: Option<Arc<TrackedAllocation>>,
 }
 
 impl Texture {
@@ -27,6 +29,11 @@ impl Texture {
     pub fn create_view(&self, desc: &wgpu::TextureViewDescriptor) -> TextureView {
         TextureView::from(self.value.create_view(desc))
     }
+
+    pub(crate) fn with_tracking(mut self, allocation: TrackedAllocation) -> Self {
+        self._tracking = Some(Arc::new(allocation));
+        self
+    }
 }
 
 impl From<wgpu::Texture> for Texture {
@@ -34,6 +41,7 @@ impl From<wgpu::Texture> for Texture {
         Texture {
             id: TextureId::new(),
             value: ErasedTexture::new(value),
+            _tracking: None,
         }
     }
 }
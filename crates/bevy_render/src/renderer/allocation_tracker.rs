@@ -0,0 +1,191 @@
+use bevy_utils::{
+    tracing::{info, warn},
+    HashMap,
+};
+use std::{
+    backtrace::Backtrace,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// The kind of GPU resource recorded by a [`GpuAllocationTracker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GpuAllocationKind {
+    /// A [`Buffer`](crate::render_resource::Buffer).
+    Buffer,
+    /// A [`Texture`](crate::render_resource::Texture).
+    Texture,
+}
+
+/// A live allocation recorded by a [`GpuAllocationTracker`].
+#[derive(Clone, Debug)]
+pub struct GpuAllocation {
+    /// Whether the allocation is a buffer or a texture.
+    pub kind: GpuAllocationKind,
+    /// The label the resource was created with, or `"<unlabeled>"`.
+    pub label: String,
+    /// The size of the allocation in bytes. For textures this is an estimate based on the
+    /// extent, mip levels and block size of the format.
+    pub size: u64,
+    /// The callstack at the point of allocation. This is only captured when backtraces are
+    /// enabled through `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`.
+    pub backtrace: Arc<Backtrace>,
+}
+
+/// A summary of the currently-live allocations sharing a label.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GpuAllocationSummary {
+    /// The number of live allocations with the label.
+    pub count: usize,
+    /// The total size of those allocations in bytes.
+    pub bytes: u64,
+}
+
+/// Records the buffers and textures created through a [`RenderDevice`](super::RenderDevice)
+/// that are still alive, to help track down GPU memory leaks.
+///
+/// The tracker is enabled by [`initialize_renderer`](super::initialize_renderer) when
+/// [`WgpuSettings::track_allocations`](crate::settings::WgpuSettings::track_allocations) is set.
+/// An allocation is considered freed once the last clone of its
+/// [`Buffer`](crate::render_resource::Buffer) or [`Texture`](crate::render_resource::Texture)
+/// is dropped.
+#[derive(Clone, Default)]
+pub struct GpuAllocationTracker {
+    live: Arc<Mutex<HashMap<u64, GpuAllocation>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl GpuAllocationTracker {
+    /// Records a new allocation. The allocation stays live until the returned
+    /// [`TrackedAllocation`] is dropped.
+    pub fn track(
+        &self,
+        kind: GpuAllocationKind,
+        label: Option<&str>,
+        size: u64,
+    ) -> TrackedAllocation {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let allocation = GpuAllocation {
+            kind,
+            label: label.unwrap_or("<unlabeled>").to_string(),
+            size,
+            backtrace: Arc::new(Backtrace::capture()),
+        };
+        self.live.lock().unwrap().insert(id, allocation);
+        TrackedAllocation {
+            tracker: self.clone(),
+            id,
+        }
+    }
+
+    /// Returns a snapshot of every currently-live allocation.
+    pub fn live_allocations(&self) -> Vec<GpuAllocation> {
+        self.live.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Returns the number and total size of the currently-live allocations, grouped by label.
+    pub fn live_allocations_by_label(&self) -> HashMap<String, GpuAllocationSummary> {
+        let mut summaries = HashMap::<String, GpuAllocationSummary>::default();
+        for allocation in self.live.lock().unwrap().values() {
+            let summary = summaries.entry(allocation.label.clone()).or_default();
+            summary.count += 1;
+            summary.bytes += allocation.size;
+        }
+        summaries
+    }
+
+    /// Logs the currently-live allocations grouped by label, largest first.
+    pub fn dump(&self) {
+        let mut summaries: Vec<_> = self.live_allocations_by_label().into_iter().collect();
+        summaries.sort_by_key(|(_, summary)| std::cmp::Reverse(summary.bytes));
+        info!("{} live GPU allocation labels", summaries.len());
+        for (label, summary) in summaries {
+            info!(
+                "  {label}: {} allocation(s), {} bytes",
+                summary.count, summary.bytes
+            );
+        }
+    }
+
+    fn free(&self, id: u64) {
+        if self.live.lock().unwrap().remove(&id).is_none() {
+            warn!("GPU allocation {id} was freed twice");
+        }
+    }
+}
+
+impl fmt::Debug for GpuAllocationTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GpuAllocationTracker")
+            .field("live", &self.live.lock().unwrap().len())
+            .finish()
+    }
+}
+
+/// Keeps an allocation recorded in a [`GpuAllocationTracker`] live until dropped.
+#[derive(Debug)]
+pub struct TrackedAllocation {
+    tracker: GpuAllocationTracker,
+    id: u64,
+}
+
+impl Drop for TrackedAllocation {
+    fn drop(&mut self) {
+        self.tracker.free(self.id);
+    }
+}
+
+/// Estimates the number of bytes used by a texture created from `desc`.
+pub(crate) fn texture_size(desc: &wgpu::TextureDescriptor) -> u64 {
+    let (block_width, block_height) = desc.format.block_dimensions();
+    let block_size = desc.format.block_copy_size(None).unwrap_or(0) as u64;
+    (0..desc.mip_level_count)
+        .map(|mip| {
+            let extent = desc.size.mip_level_size(mip, desc.dimension);
+            let blocks_x = (extent.width as u64).div_ceil(block_width as u64);
+            let blocks_y = (extent.height as u64).div_ceil(block_height as u64);
+            blocks_x * blocks_y * extent.depth_or_array_layers as u64 * block_size
+        })
+        .sum::<u64>()
+        * desc.sample_count as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GpuAllocationKind, GpuAllocationSummary, GpuAllocationTracker};
+
+    #[test]
+    fn live_set_reflects_allocations_and_frees() {
+        let tracker = GpuAllocationTracker::default();
+
+        let a = tracker.track(GpuAllocationKind::Buffer, Some("mesh_vertices"), 256);
+        let b = tracker.track(GpuAllocationKind::Buffer, Some("mesh_vertices"), 128);
+        let c = tracker.track(GpuAllocationKind::Texture, Some("shadow_map"), 1024);
+        assert_eq!(tracker.live_allocations().len(), 3);
+
+        drop(b);
+        let by_label = tracker.live_allocations_by_label();
+        assert_eq!(
+            by_label["mesh_vertices"],
+            GpuAllocationSummary {
+                count: 1,
+                bytes: 256
+            }
+        );
+        assert_eq!(
+            by_label["shadow_map"],
+            GpuAllocationSummary {
+                count: 1,
+                bytes: 1024
+            }
+        );
+
+        drop(a);
+        drop(c);
+        assert!(tracker.live_allocations().is_empty());
+        assert!(tracker.live_allocations_by_label().is_empty());
+    }
+}
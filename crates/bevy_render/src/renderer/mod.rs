@@ -1,9 +1,11 @@
+mod allocation_tracker;
 mod graph_runner;
 mod render_device;
 
+pub use allocation_tracker::*;
 use bevy_derive::{Deref, DerefMut};
 use bevy_tasks::ComputeTaskPool;
-use bevy_utils::tracing::{error, info, info_span};
+use bevy_utils::tracing::{error, info, info_span, warn};
 pub use graph_runner::*;
 pub use render_device::*;
 
@@ -128,10 +130,62 @@ pub struct RenderAdapterInfo(pub AdapterInfo);
 /// first requested backend that has an adapter with the requested power preference.
 /// 
 /// Prioritizes power preference over backend.
+///
+/// If [`WgpuSettings::adapter_selector`] is set, it picks the adapter instead.
 pub fn create_instance_and_adapter(
     requested_backends: &[Backend],
     settings: &WgpuSettings,
 ) -> Option<(Instance, Adapter)> {
+    select_adapter(requested_backends, settings, |backend| {
+        enumerate_backend_adapters(backend, settings)
+    })
+}
+
+/// Lists the adapters that [`create_instance_and_adapter`] chooses from with these settings,
+/// without creating a device on any of them, for example to let users pick a GPU with
+/// [`WgpuSettings::adapter_selector`].
+///
+/// The adapters are listed in the order of [`WgpuSettings::backends`], and only the ones matching
+/// [`WgpuSettings::adapter_name`] are included. The list is empty if no backend is requested.
+pub fn enumerate_adapters(settings: &WgpuSettings) -> Vec<RenderAdapterInfo> {
+    let requested_backends = settings.backends.as_deref().unwrap_or_default();
+    candidate_adapters(requested_backends, settings, |backend| {
+        enumerate_backend_adapters(backend, settings)
+    })
+    .into_iter()
+    .flat_map(|(_, adapters)| adapters)
+    .map(|(info, _)| RenderAdapterInfo(info))
+    .collect()
+}
+
+fn enumerate_backend_adapters(
+    backend: Backend,
+    settings: &WgpuSettings,
+) -> (Instance, Vec<(AdapterInfo, Adapter)>) {
+    let backends = backend.into();
+    let instance = Instance::new(wgpu::InstanceDescriptor {
+        backends,
+        dx12_shader_compiler: settings.dx12_shader_compiler.clone(),
+        flags: settings.instance_flags,
+        gles_minor_version: settings.gles3_minor_version,
+    });
+    let adapters = instance
+        .enumerate_adapters(backends)
+        .into_iter()
+        .map(|adapter| (adapter.get_info(), adapter))
+        .collect();
+    (instance, adapters)
+}
+
+/// Picks an adapter among the ones returned by `enumerate` for each requested backend, as
+/// described in [`create_instance_and_adapter`], and returns it with the instance it came from.
+///
+/// Backends without a suitable adapter are logged and skipped.
+fn select_adapter<I, A>(
+    requested_backends: &[Backend],
+    settings: &WgpuSettings,
+    enumerate: impl FnMut(Backend) -> (I, Vec<(AdapterInfo, A)>),
+) -> Option<(I, A)> {
     // We use the same order of device types as `wgpu` does in "wgpu-core-0.19.0\src\instance.rs:898"
     let target_device_types = match settings.power_preference {
         PowerPreference::None => [
@@ -157,31 +211,83 @@ pub fn create_instance_and_adapter(
         ],
     };
 
-    for target_device_type in target_device_types {
-        for backend in requested_backends {
-            let backends = (*backend).into();
-
-            let instance = Instance::new(wgpu::InstanceDescriptor {
-                backends,
-                dx12_shader_compiler: settings.dx12_shader_compiler.clone(),
-                flags: settings.instance_flags,
-                gles_minor_version: settings.gles3_minor_version,
+    let mut candidates = candidate_adapters(requested_backends, settings, enumerate);
+
+    if let Some(selector) = &settings.adapter_selector {
+        let infos: Vec<_> = candidates
+            .iter()
+            .flat_map(|(_, adapters)| adapters)
+            .map(|(info, _)| RenderAdapterInfo(info.clone()))
+            .collect();
+        if !infos.is_empty() {
+            let selected = selector(&infos);
+            let mut remaining = selected;
+            let found = candidates.iter().position(|(_, adapters)| {
+                if remaining < adapters.len() {
+                    return true;
+                }
+                remaining -= adapters.len();
+                false
             });
-
-            if let Some(adapter) = instance
-                .enumerate_adapters(backends)
-                .drain(..)
-                .filter(|adapter| adapter.get_info().device_type == target_device_type)
-                .next()
-            {
+            if let Some(index) = found {
+                let (instance, adapters) = candidates.swap_remove(index);
+                let (_, adapter) = adapters.into_iter().nth(remaining)?;
                 return Some((instance, adapter));
             }
+            warn!(
+                "The adapter selector picked adapter {selected}, but only {} are available, choosing by power preference instead",
+                infos.len()
+            );
+        }
+    }
+
+    for target_device_type in target_device_types {
+        let found = candidates.iter().position(|(_, adapters)| {
+            adapters
+                .iter()
+                .any(|(info, _)| info.device_type == target_device_type)
+        });
+        if let Some(index) = found {
+            let (instance, adapters) = candidates.swap_remove(index);
+            let adapter = adapters
+                .into_iter()
+                .find(|(info, _)| info.device_type == target_device_type)
+                .map(|(_, adapter)| adapter)?;
+            return Some((instance, adapter));
         }
     }
 
     None
 }
 
+/// Enumerates the adapters of each requested backend, in order, keeping the ones matching
+/// [`WgpuSettings::adapter_name`]. Backends left without adapters are logged and skipped.
+fn candidate_adapters<I, A>(
+    requested_backends: &[Backend],
+    settings: &WgpuSettings,
+    mut enumerate: impl FnMut(Backend) -> (I, Vec<(AdapterInfo, A)>),
+) -> Vec<(I, Vec<(AdapterInfo, A)>)> {
+    let mut candidates = Vec::with_capacity(requested_backends.len());
+    for &backend in requested_backends {
+        let (instance, mut adapters) = enumerate(backend);
+        if adapters.is_empty() {
+            info!("Skipping the {backend:?} backend: no adapter was found");
+            continue;
+        }
+        if let Some(name) = &settings.adapter_name {
+            let name = name.to_lowercase();
+            adapters.retain(|(info, _)| info.name.to_lowercase().contains(&name));
+            if adapters.is_empty() {
+                info!("Skipping the {backend:?} backend: no adapter name contains {name:?}");
+                continue;
+            }
+        }
+        candidates.push((instance, adapters));
+    }
+
+    candidates
+}
+
 /// Initializes the renderer by retrieving and preparing the GPU instance, device and queue
 /// for the specified backend.
 pub async fn initialize_renderer(
@@ -191,6 +297,15 @@ pub async fn initialize_renderer(
     let adapter_info = adapter.get_info();
     info!("{:?}", adapter_info);
 
+    let missing_downlevel_flags =
+        options.missing_downlevel_flags(&adapter.get_downlevel_capabilities());
+    if !missing_downlevel_flags.is_empty() {
+        panic!(
+            "The adapter {} does not support the required downlevel flags: {:?}",
+            adapter_info.name, missing_downlevel_flags
+        );
+    }
+
     #[cfg(feature = "wgpu_trace")]
     let trace_path = {
         let path = std::path::Path::new("wgpu_trace");
@@ -337,8 +452,12 @@ pub async fn initialize_renderer(
         .unwrap();
     let queue = Arc::new(queue);
     let adapter = Arc::new(adapter);
+    let mut render_device = RenderDevice::from(device);
+    if options.track_allocations {
+        render_device = render_device.with_allocation_tracker(GpuAllocationTracker::default());
+    }
     (
-        RenderDevice::from(device),
+        render_device,
         RenderQueue(queue),
         RenderAdapterInfo(adapter_info),
         RenderAdapter(adapter),
@@ -478,3 +597,140 @@ enum QueuedCommandBuffer<'w> {
     Ready(CommandBuffer),
     Task(Box<dyn FnOnce(RenderDevice) -> CommandBuffer + 'w + Send>),
 }
+
+/// Creates the renderer resources without a window for tests that need a GPU.
+///
+/// Returns `None` if no adapter is available, in which case the test should return early.
+#[cfg(test)]
+pub(crate) fn headless_render_resources() -> Option<crate::settings::RenderResources> {
+    let instance = Instance::new(wgpu::InstanceDescriptor::default());
+    let adapter = futures_lite::future::block_on(
+        instance.request_adapter(&wgpu::RequestAdapterOptions::default()),
+    )?;
+    let (device, queue) = futures_lite::future::block_on(
+        adapter.request_device(&wgpu::DeviceDescriptor::default(), None),
+    )
+    .ok()?;
+    Some(crate::settings::RenderResources(
+        RenderDevice::from(device),
+        RenderQueue(Arc::new(queue)),
+        RenderAdapterInfo(adapter.get_info()),
+        RenderAdapter(Arc::new(adapter)),
+        RenderInstance(Arc::new(instance)),
+    ))
+}
+
+/// Creates a [`RenderDevice`] and [`RenderQueue`] without a window for tests that need a GPU.
+///
+/// Returns `None` if no adapter is available, in which case the test should return early.
+#[cfg(test)]
+pub(crate) fn headless_render_device() -> Option<(RenderDevice, RenderQueue)> {
+    let crate::settings::RenderResources(device, queue, ..) = headless_render_resources()?;
+    Some((device, queue))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select_adapter, RenderAdapterInfo};
+    use crate::settings::WgpuSettings;
+    use std::sync::{Arc, Mutex};
+    use wgpu::{AdapterInfo, Backend, DeviceType, PowerPreference};
+
+    #[test]
+    fn falls_back_to_the_next_backend_with_an_adapter() {
+        let settings = WgpuSettings::default();
+        let adapter = |backend, device_type| AdapterInfo {
+            name: format!("{backend:?} adapter"),
+            vendor: 0,
+            device: 0,
+            device_type,
+            driver: String::new(),
+            driver_info: String::new(),
+            backend,
+        };
+
+        // Vulkan fails to produce an adapter, so GL is used.
+        let (instance, chosen) =
+            select_adapter(&[Backend::Vulkan, Backend::Gl], &settings, |backend| {
+                let adapters = match backend {
+                    Backend::Gl => vec![(adapter(backend, DeviceType::IntegratedGpu), backend)],
+                    _ => Vec::new(),
+                };
+                (backend, adapters)
+            })
+            .unwrap();
+        assert_eq!((instance, chosen), (Backend::Gl, Backend::Gl));
+
+        // The order of backends is kept among adapters of the same device type.
+        let (_, chosen) = select_adapter(&[Backend::Vulkan, Backend::Gl], &settings, |backend| {
+            (
+                backend,
+                vec![(adapter(backend, DeviceType::DiscreteGpu), backend)],
+            )
+        })
+        .unwrap();
+        assert_eq!(chosen, Backend::Vulkan);
+
+        let none = select_adapter(&[Backend::Vulkan], &settings, |backend| {
+            (backend, Vec::<(AdapterInfo, ())>::new())
+        });
+        assert!(none.is_none());
+    }
+
+    #[test]
+    fn adapter_selector_picks_from_every_backend() {
+        let adapter = |name: &str, device_type| AdapterInfo {
+            name: name.to_string(),
+            vendor: 0,
+            device: 0,
+            device_type,
+            driver: String::new(),
+            driver_info: String::new(),
+            backend: Backend::Vulkan,
+        };
+        let enumerate = |backend| {
+            let adapters = match backend {
+                Backend::Vulkan => vec![
+                    (adapter("integrated", DeviceType::IntegratedGpu), 0),
+                    (adapter("software", DeviceType::Cpu), 1),
+                ],
+                _ => vec![(adapter("discrete", DeviceType::DiscreteGpu), 2)],
+            };
+            (backend, adapters)
+        };
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let selector = {
+            let seen = seen.clone();
+            move |adapters: &[RenderAdapterInfo]| {
+                seen.lock()
+                    .unwrap()
+                    .extend(adapters.iter().map(|info| info.name.clone()));
+                adapters
+                    .iter()
+                    .position(|info| info.device_type == DeviceType::DiscreteGpu)
+                    .unwrap_or(0)
+            }
+        };
+        let mut settings = WgpuSettings {
+            power_preference: PowerPreference::LowPower,
+            adapter_selector: Some(Arc::new(selector)),
+            ..Default::default()
+        };
+
+        // The power preference alone would pick the integrated GPU.
+        let (instance, chosen) =
+            select_adapter(&[Backend::Vulkan, Backend::Dx12], &settings, enumerate).unwrap();
+        assert_eq!((instance, chosen), (Backend::Dx12, 2));
+        assert_eq!(
+            *seen.lock().unwrap(),
+            ["integrated", "software", "discrete"]
+        );
+
+        // Out of range indices fall back to the power preference.
+        settings.adapter_selector = Some(Arc::new(|_: &[RenderAdapterInfo]| 10));
+        let (_, chosen) =
+            select_adapter(&[Backend::Vulkan, Backend::Dx12], &settings, enumerate).unwrap();
+        assert_eq!(chosen, 0);
+    }
+}
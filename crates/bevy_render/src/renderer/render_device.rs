@@ -3,32 +3,76 @@ use crate::render_resource::{
     RenderPipeline, Sampler, Texture,
 };
 use bevy_ecs::system::Resource;
+use bevy_utils::{Duration, Instant};
+use thiserror::Error;
 use wgpu::{
     util::DeviceExt, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor,
-    BindGroupLayoutEntry, BufferAsyncError, BufferBindingType, MaintainResult,
+    BindGroupLayoutEntry, BufferAsyncError, BufferBindingType, Maintain, MaintainResult,
 };
 
-use super::RenderQueue;
+use super::{texture_size, GpuAllocationKind, GpuAllocationTracker, RenderQueue};
 
 use crate::render_resource::resource_macros::*;
 
 render_resource_wrapper!(ErasedRenderDevice, wgpu::Device);
 
+/// The error returned by [`RenderDevice::poll_until_idle`] if the submitted work didn't complete
+/// in time.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("GPU work did not complete within {0:?}")]
+pub struct PollTimeoutError(pub Duration);
+
 /// This GPU device is responsible for the creation of most rendering and compute resources.
 #[derive(Resource, Clone)]
 pub struct RenderDevice {
     device: ErasedRenderDevice,
+    allocation_tracker: Option<GpuAllocationTracker>,
 }
 
 impl From<wgpu::Device> for RenderDevice {
     fn from(device: wgpu::Device) -> Self {
         Self {
             device: ErasedRenderDevice::new(device),
+            allocation_tracker: None,
         }
     }
 }
 
 impl RenderDevice {
+    /// Records every [`Buffer`] and [`Texture`] created through this device in `tracker`
+    /// for as long as they are alive.
+    pub fn with_allocation_tracker(mut self, tracker: GpuAllocationTracker) -> Self {
+        self.allocation_tracker = Some(tracker);
+        self
+    }
+
+    /// Returns the [`GpuAllocationTracker`] of this device, if allocation tracking is enabled.
+    #[inline]
+    pub fn allocation_tracker(&self) -> Option<&GpuAllocationTracker> {
+        self.allocation_tracker.as_ref()
+    }
+
+    fn track_buffer(&self, buffer: Buffer, label: Option<&str>) -> Buffer {
+        match &self.allocation_tracker {
+            Some(tracker) => {
+                let size = buffer.size();
+                buffer.with_tracking(tracker.track(GpuAllocationKind::Buffer, label, size))
+            }
+            None => buffer,
+        }
+    }
+
+    fn track_texture(&self, texture: Texture, desc: &wgpu::TextureDescriptor) -> Texture {
+        match &self.allocation_tracker {
+            Some(tracker) => texture.with_tracking(tracker.track(
+                GpuAllocationKind::Texture,
+                desc.label,
+                texture_size(desc),
+            )),
+            None => texture,
+        }
+    }
+
     /// List all [`Features`](wgpu::Features) that may be used with this device.
     ///
     /// Functions may panic if you use unsupported features.
@@ -65,6 +109,37 @@ impl RenderDevice {
         self.device.poll(maintain)
     }
 
+    /// Blocks until all work submitted to the queue has completed, or until `timeout` has
+    /// elapsed, whichever comes first.
+    ///
+    /// Without a timeout, this waits with [`Maintain::Wait`]. With one, the device is polled
+    /// with [`Maintain::Poll`] until the queue is empty, sleeping for increasingly long
+    /// intervals of at most a millisecond in between, since wgpu can't wait with a timeout.
+    ///
+    /// On the web the browser polls the device itself, so this returns right away without
+    /// waiting for the submitted work.
+    pub fn poll_until_idle(&self, timeout: Option<Duration>) -> Result<(), PollTimeoutError> {
+        const MAX_SLEEP: Duration = Duration::from_millis(1);
+
+        let Some(timeout) = timeout else {
+            self.poll(Maintain::Wait);
+            return Ok(());
+        };
+
+        let start = Instant::now();
+        let mut sleep = Duration::from_micros(10);
+        loop {
+            if self.poll(Maintain::Poll).is_queue_empty() {
+                return Ok(());
+            }
+            let Some(remaining) = timeout.checked_sub(start.elapsed()) else {
+                return Err(PollTimeoutError(timeout));
+            };
+            std::thread::sleep(sleep.min(remaining));
+            sleep = (sleep * 2).min(MAX_SLEEP);
+        }
+    }
+
     /// Creates an empty [`CommandEncoder`](wgpu::CommandEncoder).
     #[inline]
     pub fn create_command_encoder(
@@ -144,13 +219,13 @@ impl RenderDevice {
     /// Creates a [`Buffer`].
     pub fn create_buffer(&self, desc: &wgpu::BufferDescriptor) -> Buffer {
         let wgpu_buffer = self.device.create_buffer(desc);
-        Buffer::from(wgpu_buffer)
+        self.track_buffer(Buffer::from(wgpu_buffer), desc.label)
     }
 
     /// Creates a [`Buffer`] and initializes it with the specified data.
     pub fn create_buffer_with_data(&self, desc: &wgpu::util::BufferInitDescriptor) -> Buffer {
         let wgpu_buffer = self.device.create_buffer_init(desc);
-        Buffer::from(wgpu_buffer)
+        self.track_buffer(Buffer::from(wgpu_buffer), desc.label)
     }
 
     /// Creates a new [`Texture`] and initializes it with the specified data.
@@ -167,7 +242,7 @@ impl RenderDevice {
         let wgpu_texture =
             self.device
                 .create_texture_with_data(render_queue.as_ref(), desc, order, data);
-        Texture::from(wgpu_texture)
+        self.track_texture(Texture::from(wgpu_texture), desc)
     }
 
     /// Creates a new [`Texture`].
@@ -175,7 +250,7 @@ impl RenderDevice {
     /// `desc` specifies the general format of the texture.
     pub fn create_texture(&self, desc: &wgpu::TextureDescriptor) -> Texture {
         let wgpu_texture = self.device.create_texture(desc);
-        Texture::from(wgpu_texture)
+        self.track_texture(Texture::from(wgpu_texture), desc)
     }
 
     /// Creates a new [`Sampler`].
@@ -227,3 +302,43 @@ impl RenderDevice {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::renderer::headless_render_device;
+    use bevy_utils::Duration;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+    use wgpu::{BufferDescriptor, BufferUsages, CommandEncoderDescriptor, MapMode};
+
+    #[test]
+    fn poll_until_idle_completes_submitted_work() {
+        let Some((render_device, render_queue)) = headless_render_device() else {
+            return;
+        };
+        let buffer = render_device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        for timeout in [Some(Duration::from_secs(5)), None] {
+            let mapped = Arc::new(AtomicBool::new(false));
+            let callback_mapped = mapped.clone();
+            buffer.slice(..).map_async(MapMode::Read, move |result| {
+                callback_mapped.store(result.is_ok(), Ordering::Relaxed);
+            });
+            let encoder =
+                render_device.create_command_encoder(&CommandEncoderDescriptor::default());
+            render_queue.submit([encoder.finish()]);
+
+            // Mapping callbacks are only called while polling the device.
+            assert_eq!(render_device.poll_until_idle(timeout), Ok(()));
+            assert!(mapped.load(Ordering::Relaxed));
+            buffer.unmap();
+        }
+    }
+}
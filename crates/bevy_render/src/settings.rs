@@ -1,11 +1,18 @@
 use crate::renderer::{
     RenderAdapter, RenderAdapterInfo, RenderDevice, RenderInstance, RenderQueue,
 };
-use std::borrow::Cow;
+use bevy_utils::tracing::warn;
+use std::{
+    borrow::Cow,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
 
 pub use wgpu::{
-    Backend, Backends, Dx12Compiler, Features as WgpuFeatures, Gles3MinorVersion, InstanceFlags,
-    Limits as WgpuLimits, PowerPreference,
+    Backend, Backends, DownlevelCapabilities, DownlevelFlags, Dx12Compiler,
+    Features as WgpuFeatures, Gles3MinorVersion, InstanceFlags, Limits as WgpuLimits,
+    PowerPreference,
 };
 
 /// Configures the priority used when automatically configuring the features/limits of `wgpu`.
@@ -19,6 +26,10 @@ pub enum WgpuSettingsPriority {
     WebGL2,
 }
 
+/// Picks the adapter to render with among the candidates it's given, by returning its index.
+/// See [`WgpuSettings::adapter_selector`].
+pub type AdapterSelector = Arc<dyn Fn(&[RenderAdapterInfo]) -> usize + Send + Sync>;
+
 /// Provides configuration for renderer initialization. Use [`RenderDevice::features`](RenderDevice::features),
 /// [`RenderDevice::limits`](RenderDevice::limits), and the [`RenderAdapterInfo`]
 /// resource to get runtime information about the actual adapter, backend, features, and limits.
@@ -50,6 +61,95 @@ pub struct WgpuSettings {
     pub gles3_minor_version: Gles3MinorVersion,
     /// These are for controlling WGPU's debug information to eg. enable validation and shader debug info in release builds.
     pub instance_flags: InstanceFlags,
+    /// The downlevel capabilities the adapter must have, such as
+    /// [`DownlevelFlags::COMPUTE_SHADERS`] on GLES targets.
+    ///
+    /// Renderer initialization panics with the list of missing flags if the adapter doesn't
+    /// support all of them, instead of failing later when a pipeline needs them.
+    pub required_downlevel_flags: DownlevelFlags,
+    /// If set, only adapters whose name contains this string, ignoring case, are used.
+    pub adapter_name: Option<String>,
+    /// If set, picks the adapter to render with instead of the
+    /// [`power_preference`](Self::power_preference).
+    ///
+    /// It's called with the adapters listed by
+    /// [`enumerate_adapters`](crate::renderer::enumerate_adapters), unless there are none. If it
+    /// returns an index out of range, the adapter is chosen as if it wasn't set.
+    pub adapter_selector: Option<AdapterSelector>,
+    /// If `true`, the app keeps running without rendering when no adapter is found, instead of
+    /// panicking.
+    ///
+    /// The app is then set up as if [`backends`](Self::backends) was `None`: the render sub-app
+    /// isn't created, so the systems of the [`Render`](crate::Render) and
+    /// [`ExtractSchedule`](crate::ExtractSchedule) schedules never run and resources like
+    /// [`RenderDevice`] don't exist. Systems in the main app, including those of rendering
+    /// plugins such as visibility and camera updates, keep running.
+    ///
+    /// This is ignored on wasm, where the adapter is only found after the plugins are built.
+    pub fallback_to_headless: bool,
+    /// If `true`, the [`RenderDevice`] records every buffer and texture it creates in a
+    /// [`GpuAllocationTracker`](crate::renderer::GpuAllocationTracker), to help track down GPU
+    /// memory leaks.
+    ///
+    /// This is off by default, since it adds a lock and a backtrace capture to every allocation.
+    pub track_allocations: bool,
+}
+
+impl WgpuSettings {
+    /// Creates the default settings, overridden by the following environment variables:
+    ///
+    /// - `WGPU_BACKEND`: a comma-separated list of `vulkan`, `metal`, `dx12`, `gl` or `webgpu`.
+    /// - `WGPU_POWER_PREF`: `low`, `high` or `none`.
+    /// - `WGPU_ADAPTER_NAME`: a substring of the name of the adapter to use.
+    ///
+    /// Unknown values are ignored with a warning.
+    pub fn from_env() -> Self {
+        Self::from_env_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Like [`from_env`](Self::from_env), but reads the variables with `lookup`.
+    fn from_env_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let mut settings = Self::default();
+
+        if let Some(value) = lookup("WGPU_BACKEND") {
+            let backends: Vec<_> = value
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .filter_map(|name| {
+                    let backend = backend_from_str(name);
+                    if backend.is_none() {
+                        warn!("Ignoring unknown backend `{name}` in WGPU_BACKEND");
+                    }
+                    backend
+                })
+                .collect();
+            if !backends.is_empty() {
+                settings.backends = Some(backends);
+            }
+        }
+
+        if let Some(value) = lookup("WGPU_POWER_PREF") {
+            match value.trim().to_lowercase().as_str() {
+                "low" => settings.power_preference = PowerPreference::LowPower,
+                "high" => settings.power_preference = PowerPreference::HighPerformance,
+                "none" => settings.power_preference = PowerPreference::None,
+                _ => warn!("Ignoring unknown power preference `{value}` in WGPU_POWER_PREF"),
+            }
+        }
+
+        if let Some(value) = lookup("WGPU_ADAPTER_NAME") {
+            settings.adapter_name = Some(value);
+        }
+
+        settings
+    }
+
+    /// Returns the [`required_downlevel_flags`](Self::required_downlevel_flags) that are not
+    /// supported according to `capabilities`.
+    pub fn missing_downlevel_flags(&self, capabilities: &DownlevelCapabilities) -> DownlevelFlags {
+        self.required_downlevel_flags - capabilities.flags
+    }
 }
 
 impl Default for WgpuSettings {
@@ -130,6 +230,11 @@ impl Default for WgpuSettings {
             dx12_shader_compiler: dx12_compiler,
             gles3_minor_version,
             instance_flags,
+            required_downlevel_flags: DownlevelFlags::empty(),
+            adapter_name: None,
+            adapter_selector: None,
+            fallback_to_headless: false,
+            track_allocations: false,
         }
     }
 }
@@ -146,8 +251,21 @@ pub enum RenderCreation {
     ),
     /// Lets the rendering plugin create resources itself.
     Automatic(WgpuSettings),
+    /// Like [`RenderCreation::Manual`], but the resources are created by a future, for example
+    /// when `wgpu` is initialized in an async context. See [`RenderCreation::manual_async`].
+    ManualAsync(Mutex<Option<Pin<Box<dyn Future<Output = RenderResources> + Send>>>>),
 }
 
+/// The renderer resources created outside of the rendering plugin by the future of a
+/// [`RenderCreation::ManualAsync`].
+pub struct RenderResources(
+    pub RenderDevice,
+    pub RenderQueue,
+    pub RenderAdapterInfo,
+    pub RenderAdapter,
+    pub RenderInstance,
+);
+
 impl RenderCreation {
     /// Function to create a [`RenderCreation::Manual`] variant.
     pub fn manual(
@@ -159,6 +277,16 @@ impl RenderCreation {
     ) -> Self {
         Self::Manual(device, queue, adapter_info, adapter, instance)
     }
+
+    /// Function to create a [`RenderCreation::ManualAsync`] variant.
+    ///
+    /// The [`RenderPlugin`](crate::RenderPlugin) only becomes ready once the future resolves.
+    /// Until then, the future is polled each time the app checks whether its plugins are ready,
+    /// so the main thread isn't blocked while waiting for it. On wasm, it's spawned on the
+    /// [`IoTaskPool`](bevy_tasks::IoTaskPool) instead.
+    pub fn manual_async(future: impl Future<Output = RenderResources> + Send + 'static) -> Self {
+        Self::ManualAsync(Mutex::new(Some(Box::pin(future))))
+    }
 }
 
 impl Default for RenderCreation {
@@ -173,6 +301,17 @@ impl From<WgpuSettings> for RenderCreation {
     }
 }
 
+fn backend_from_str(name: &str) -> Option<Backend> {
+    Some(match name.to_lowercase().as_str() {
+        "vulkan" | "vk" => Backend::Vulkan,
+        "metal" | "mtl" => Backend::Metal,
+        "dx12" | "d3d12" => Backend::Dx12,
+        "gl" | "gles" | "opengl" => Backend::Gl,
+        "webgpu" => Backend::BrowserWebGpu,
+        _ => return None,
+    })
+}
+
 /// Get a features/limits priority from the environment variable `WGPU_SETTINGS_PRIO`
 pub fn settings_priority_from_env() -> Option<WgpuSettingsPriority> {
     Some(
@@ -188,3 +327,58 @@ pub fn settings_priority_from_env() -> Option<WgpuSettingsPriority> {
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Backend, DownlevelCapabilities, DownlevelFlags, PowerPreference, WgpuSettings};
+
+    #[test]
+    fn missing_downlevel_flags_are_reported() {
+        let settings = WgpuSettings {
+            required_downlevel_flags: DownlevelFlags::COMPUTE_SHADERS
+                | DownlevelFlags::FRAGMENT_WRITABLE_STORAGE,
+            ..Default::default()
+        };
+        let gles = DownlevelCapabilities {
+            flags: DownlevelFlags::FRAGMENT_WRITABLE_STORAGE,
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.missing_downlevel_flags(&gles),
+            DownlevelFlags::COMPUTE_SHADERS
+        );
+        assert!(settings
+            .missing_downlevel_flags(&DownlevelCapabilities::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn from_env_overrides_defaults() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        let settings = WgpuSettings::from_env_lookup(env(&[
+            ("WGPU_BACKEND", "gl, vulkan, glide"),
+            ("WGPU_POWER_PREF", "low"),
+            ("WGPU_ADAPTER_NAME", "llvmpipe"),
+        ]));
+        assert_eq!(settings.backends, Some(vec![Backend::Gl, Backend::Vulkan]));
+        assert_eq!(settings.power_preference, PowerPreference::LowPower);
+        assert_eq!(settings.adapter_name.as_deref(), Some("llvmpipe"));
+
+        // Unknown values are ignored.
+        let settings = WgpuSettings::from_env_lookup(env(&[
+            ("WGPU_BACKEND", "glide"),
+            ("WGPU_POWER_PREF", "maximum"),
+        ]));
+        let default = WgpuSettings::default();
+        assert_eq!(settings.backends, default.backends);
+        assert_eq!(settings.power_preference, default.power_preference);
+        assert_eq!(settings.adapter_name, None);
+    }
+}
@@ -14,6 +14,7 @@ pub struct ColorAttachment {
     pub texture: CachedTexture,
     pub resolve_target: Option<CachedTexture>,
     clear_color: Option<Color>,
+    manual_resolve: bool,
     is_first_call: Arc<AtomicBool>,
 }
 
@@ -27,10 +28,18 @@ impl ColorAttachment {
             texture,
             resolve_target,
             clear_color,
+            manual_resolve: false,
             is_first_call: Arc::new(AtomicBool::new(true)),
         }
     }
 
+    /// Stops [`get_attachment`](Self::get_attachment) from resolving the multisampled
+    /// `resolve_target` into `texture`, leaving the resolve to the caller.
+    pub fn with_manual_resolve(mut self) -> Self {
+        self.manual_resolve = true;
+        self
+    }
+
     /// Get this texture view as an attachment. The attachment will be cleared with a value of
     /// `clear_color` if this is the first time calling this function, otherwise it will be loaded.
     ///
@@ -41,7 +50,7 @@ impl ColorAttachment {
 
             RenderPassColorAttachment {
                 view: &resolve_target.default_view,
-                resolve_target: Some(&self.texture.default_view),
+                resolve_target: (!self.manual_resolve).then_some(&self.texture.default_view),
                 ops: Operations {
                     load: match (self.clear_color, first_call) {
                         (Some(clear_color), true) => LoadOp::Clear(clear_color.into()),
@@ -8,7 +8,7 @@ pub use window::*;
 use crate::{
     camera::{
         CameraMainTextureUsages, ClearColor, ClearColorConfig, Exposure, ExtractedCamera,
-        ManualTextureViews, MipBias, TemporalJitter,
+        ManualMsaaResolve, ManualTextureViews, MipBias, TemporalJitter,
     },
     extract_resource::{ExtractResource, ExtractResourcePlugin},
     prelude::{Image, Shader},
@@ -16,7 +16,7 @@ use crate::{
     render_asset::RenderAssets,
     render_phase::ViewRangefinder3d,
     render_resource::{DynamicUniformBuffer, ShaderType, Texture, TextureView},
-    renderer::{RenderDevice, RenderQueue},
+    renderer::{RenderAdapter, RenderDevice, RenderQueue},
     texture::{BevyDefault, CachedTexture, ColorAttachment, DepthAttachment, TextureCache},
     Render, RenderApp, RenderSet,
 };
@@ -25,14 +25,14 @@ use bevy_ecs::prelude::*;
 use bevy_math::{Mat4, UVec4, Vec3, Vec4, Vec4Swizzles};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_transform::components::GlobalTransform;
-use bevy_utils::HashMap;
+use bevy_utils::{tracing::warn, HashMap};
 use std::sync::{
     atomic::{AtomicUsize, Ordering},
     Arc,
 };
 use wgpu::{
-    Extent3d, RenderPassColorAttachment, RenderPassDepthStencilAttachment, StoreOp,
-    TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    Extent3d, Features, RenderPassColorAttachment, RenderPassDepthStencilAttachment, StoreOp,
+    TextureDescriptor, TextureDimension, TextureFormat, TextureFormatFeatureFlags, TextureUsages,
 };
 
 pub const VIEW_TYPE_HANDLE: Handle<Shader> = Handle::weak_from_u128(15421373904451797197);
@@ -59,6 +59,9 @@ impl Plugin for ViewPlugin {
             render_app.init_resource::<ViewUniforms>().add_systems(
                 Render,
                 (
+                    clamp_msaa_to_supported
+                        .in_set(RenderSet::ManageViews)
+                        .before(prepare_view_targets),
                     prepare_view_targets
                         .in_set(RenderSet::ManageViews)
                         .after(prepare_windows)
@@ -104,6 +107,58 @@ impl Msaa {
     pub fn samples(&self) -> u32 {
         *self as u32
     }
+
+    /// Returns the highest sample count that doesn't exceed this one and is supported by a
+    /// texture format with the given features.
+    ///
+    /// [`Msaa::Off`] is always supported.
+    pub fn clamp_to_supported(self, format_features: TextureFormatFeatureFlags) -> Msaa {
+        [Msaa::Sample8, Msaa::Sample4, Msaa::Sample2]
+            .into_iter()
+            .find(|msaa| *msaa <= self && format_features.sample_count_supported(msaa.samples()))
+            .unwrap_or(Msaa::Off)
+    }
+}
+
+/// Lowers the render world [`Msaa`] to a sample count supported by the main textures of views,
+/// so that an unsupported setting degrades with a warning instead of causing validation errors.
+///
+/// The main world [`Msaa`] is left as is, and only gets clamped again when it changes.
+pub fn clamp_msaa_to_supported(
+    mut msaa: ResMut<Msaa>,
+    render_device: Res<RenderDevice>,
+    render_adapter: Res<RenderAdapter>,
+) {
+    let format_features = [
+        ViewTarget::TEXTURE_FORMAT_HDR,
+        TextureFormat::bevy_default(),
+    ]
+    .into_iter()
+    .map(|format| {
+        if render_device
+            .features()
+            .contains(Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+        {
+            render_adapter.get_texture_format_features(format).flags
+        } else {
+            format
+                .guaranteed_format_features(render_device.features())
+                .flags
+        }
+    })
+    .fold(TextureFormatFeatureFlags::all(), |supported, flags| {
+        supported & flags
+    });
+
+    let supported = msaa.clamp_to_supported(format_features);
+    if supported != *msaa {
+        warn!(
+            "MSAA with {} samples is not supported by the main texture formats, using {} samples instead",
+            msaa.samples(),
+            supported.samples()
+        );
+        *msaa = supported;
+    }
 }
 
 #[derive(Component)]
@@ -210,6 +265,9 @@ impl ViewTarget {
     pub const TEXTURE_FORMAT_HDR: TextureFormat = TextureFormat::Rgba16Float;
 
     /// Retrieve this target's main texture's color attachment.
+    ///
+    /// With MSAA, this renders to the sampled texture and resolves it into the main texture,
+    /// unless the camera has [`ManualMsaaResolve`].
     pub fn get_color_attachment(&self) -> RenderPassColorAttachment {
         if self.main_texture.load(Ordering::SeqCst) == 0 {
             self.main_textures.a.get_attachment()
@@ -470,11 +528,12 @@ pub fn prepare_view_targets(
         &ExtractedCamera,
         &ExtractedView,
         &CameraMainTextureUsages,
+        Has<ManualMsaaResolve>,
     )>,
     manual_texture_views: Res<ManualTextureViews>,
 ) {
     let mut textures = HashMap::default();
-    for (entity, camera, view, texture_usage) in cameras.iter() {
+    for (entity, camera, view, texture_usage, manual_resolve) in cameras.iter() {
         if let (Some(target_size), Some(target)) = (camera.physical_target_size, &camera.target) {
             if let (Some(out_texture_view), Some(out_texture_format)) = (
                 target.get_texture_view(&windows, &images, &manual_texture_views),
@@ -498,8 +557,10 @@ pub fn prepare_view_targets(
                     _ => Some(clear_color_global.0),
                 };
 
+                // Cameras with a `RenderScale` render to smaller textures than the other cameras of
+                // their target.
                 let (a, b, sampled) = textures
-                    .entry((camera.target.clone(), view.hdr))
+                    .entry((camera.target.clone(), view.hdr, manual_resolve, target_size))
                     .or_insert_with(|| {
                         let descriptor = TextureDescriptor {
                             label: None,
@@ -530,6 +591,11 @@ pub fn prepare_view_targets(
                             },
                         );
                         let sampled = if msaa.samples() > 1 {
+                            let mut usage =
+                                TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC;
+                            if manual_resolve {
+                                usage |= TextureUsages::TEXTURE_BINDING;
+                            }
                             let sampled = texture_cache.get(
                                 &render_device,
                                 TextureDescriptor {
@@ -539,8 +605,7 @@ pub fn prepare_view_targets(
                                     sample_count: msaa.samples(),
                                     dimension: TextureDimension::D2,
                                     format: main_texture_format,
-                                    usage: TextureUsages::RENDER_ATTACHMENT
-                                        | TextureUsages::COPY_SRC,
+                                    usage,
                                     view_formats: descriptor.view_formats,
                                 },
                             );
@@ -551,11 +616,15 @@ pub fn prepare_view_targets(
                         (a, b, sampled)
                     });
 
-                let main_textures = MainTargetTextures {
+                let mut main_textures = MainTargetTextures {
                     a: ColorAttachment::new(a.clone(), sampled.clone(), clear_color),
                     b: ColorAttachment::new(b.clone(), sampled.clone(), clear_color),
                     main_texture: Arc::new(AtomicUsize::new(0)),
                 };
+                if manual_resolve {
+                    main_textures.a = main_textures.a.with_manual_resolve();
+                    main_textures.b = main_textures.b.with_manual_resolve();
+                }
 
                 commands.entity(entity).insert(ViewTarget {
                     main_texture: main_textures.main_texture.clone(),
@@ -568,3 +637,21 @@ pub fn prepare_view_targets(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Msaa;
+    use wgpu::TextureFormatFeatureFlags;
+
+    #[test]
+    fn msaa_is_clamped_to_supported_sample_counts() {
+        let single_sampled = TextureFormatFeatureFlags::FILTERABLE;
+        assert_eq!(Msaa::Sample8.clamp_to_supported(single_sampled), Msaa::Off);
+        assert_eq!(Msaa::Off.clamp_to_supported(single_sampled), Msaa::Off);
+
+        let up_to_4 =
+            TextureFormatFeatureFlags::MULTISAMPLE_X2 | TextureFormatFeatureFlags::MULTISAMPLE_X4;
+        assert_eq!(Msaa::Sample8.clamp_to_supported(up_to_4), Msaa::Sample4);
+        assert_eq!(Msaa::Sample2.clamp_to_supported(up_to_4), Msaa::Sample2);
+    }
+}
@@ -6,6 +6,15 @@ pub use slice::{ParallelSlice, ParallelSliceMut};
 mod task;
 pub use task::Task;
 
+mod task_cache;
+pub use task_cache::TaskCache;
+
+mod task_group;
+pub use task_group::TaskGroup;
+
+mod nursery;
+pub use nursery::Nursery;
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
 mod task_pool;
 #[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
@@ -0,0 +1,128 @@
+use crate::Task;
+use futures_lite::future;
+use std::{future::Future, pin::Pin, task::Poll};
+
+/// Owns a set of fallible child [`Task`]s so that they fail together: the first child to return
+/// an error cancels its siblings, and the error is propagated to whoever joins the nursery.
+///
+/// Children are spawned on any task pool, then handed to [`Nursery::spawn`].
+///
+/// Like a [`Task`], dropping the nursery, or the future returned by [`Nursery::join_all`], cancels
+/// every child still running.
+#[derive(Debug)]
+#[must_use = "Tasks are canceled when dropped, use `.join_all()` to wait for them."]
+pub struct Nursery<T, E> {
+    tasks: Vec<Task<Result<T, E>>>,
+}
+
+impl<T, E> Default for Nursery<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E> Nursery<T, E> {
+    /// Creates a nursery without children.
+    pub fn new() -> Self {
+        Self { tasks: Vec::new() }
+    }
+
+    /// Adds `task` to the children of the nursery.
+    pub fn spawn(&mut self, task: Task<Result<T, E>>) {
+        self.tasks.push(task);
+    }
+
+    /// The number of children that haven't been joined yet.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if the nursery has no children left to join.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Waits for every child to complete and returns their outputs in the order they were
+    /// spawned.
+    ///
+    /// As soon as a child returns an error, the remaining children are canceled, this waits for
+    /// them to stop running, and the error is returned. See [`Task::cancel`].
+    pub async fn join_all(&mut self) -> Result<Vec<T>, E> {
+        let mut outputs: Vec<Option<T>> = self.tasks.iter().map(|_| None).collect();
+        let mut pending: Vec<_> = std::mem::take(&mut self.tasks)
+            .into_iter()
+            .enumerate()
+            .collect();
+
+        let error = future::poll_fn(|cx| {
+            let mut i = 0;
+            while i < pending.len() {
+                let (index, task) = &mut pending[i];
+                match Pin::new(task).poll(cx) {
+                    Poll::Ready(Ok(output)) => {
+                        outputs[*index] = Some(output);
+                        drop(pending.swap_remove(i));
+                    }
+                    Poll::Ready(Err(error)) => {
+                        drop(pending.swap_remove(i));
+                        return Poll::Ready(Some(error));
+                    }
+                    Poll::Pending => i += 1,
+                }
+            }
+            if pending.is_empty() {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+
+        if let Some(error) = error {
+            for (_, task) in pending {
+                task.cancel().await;
+            }
+            return Err(error);
+        }
+        Ok(outputs.into_iter().map(Option::unwrap).collect())
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), feature = "multi-threaded"))]
+mod tests {
+    use super::Nursery;
+    use crate::{block_on, TaskPool};
+    use futures_lite::future;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    struct CountOnDrop(Arc<AtomicUsize>);
+
+    impl Drop for CountOnDrop {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn first_error_cancels_siblings() {
+        let pool = TaskPool::new();
+        let mut nursery = Nursery::new();
+        let dropped = Arc::new(AtomicUsize::new(0));
+        for _ in 0..2 {
+            let guard = CountOnDrop(dropped.clone());
+            nursery.spawn(pool.spawn(async move {
+                let _guard = guard;
+                // Never completes, so it can only stop by being canceled.
+                future::pending::<Result<i32, &str>>().await
+            }));
+        }
+        nursery.spawn(pool.spawn(async { Err("failed") }));
+
+        assert_eq!(block_on(nursery.join_all()), Err("failed"));
+        assert_eq!(dropped.load(Ordering::SeqCst), 2);
+        assert!(nursery.is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::Task;
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+/// Memoizes the results of [`Task`]s by key, so that a computation that produces the same result
+/// for the same inputs, like a BVH for a static mesh, only runs once while its key stays in use.
+///
+/// Each key is typically a hash of the inputs of the computation. When the cache is full, the
+/// least recently used result is evicted to make room for a new one.
+///
+/// Like a [`Task`], removing a computation that hasn't completed yet cancels it.
+#[derive(Debug)]
+pub struct TaskCache<K, T> {
+    entries: HashMap<K, CacheEntry<T>>,
+    capacity: usize,
+    uses: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry<T> {
+    result: CachedResult<T>,
+    last_use: u64,
+}
+
+#[derive(Debug)]
+enum CachedResult<T> {
+    Pending(Task<T>),
+    Ready(T),
+    /// Only used while moving the output out of a completed task.
+    Taken,
+}
+
+impl<K: Hash + Eq, T> TaskCache<K, T> {
+    /// Creates a cache holding the results of at most `capacity` computations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "a TaskCache must have a capacity of at least 1"
+        );
+        Self {
+            entries: HashMap::new(),
+            capacity,
+            uses: 0,
+        }
+    }
+
+    /// Returns the result of the computation for `key`, or `None` if it hasn't completed yet.
+    ///
+    /// If there is no computation for `key`, `spawn` is called to start it, evicting the least
+    /// recently used entry if the cache is full. Otherwise `spawn` isn't called.
+    pub fn get_or_spawn(&mut self, key: K, spawn: impl FnOnce() -> Task<T>) -> Option<&T> {
+        self.uses += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            self.evict_least_recently_used();
+        }
+
+        let entry = self.entries.entry(key).or_insert_with(|| CacheEntry {
+            result: CachedResult::Pending(spawn()),
+            last_use: 0,
+        });
+        entry.last_use = self.uses;
+        entry.result.poll_ready()
+    }
+
+    /// Returns the result of the computation for `key`, if it has completed.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&T>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.get_mut(key)?.result.poll_ready()
+    }
+
+    /// Removes the computation for `key`, canceling it if it hasn't completed.
+    ///
+    /// Returns `true` if there was one.
+    pub fn invalidate<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.remove(key).is_some()
+    }
+
+    /// Removes every computation, canceling the ones that haven't completed.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// The number of computations in the cache, completed or not.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the cache has no computations.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The maximum number of computations kept in the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        // Every use bumps the counter, so no two entries share a `last_use`.
+        if let Some(oldest) = self.entries.values().map(|entry| entry.last_use).min() {
+            self.entries.retain(|_, entry| entry.last_use != oldest);
+        }
+    }
+}
+
+impl<T> CachedResult<T> {
+    /// Takes the output of the task once it has completed, and returns it if it's ready.
+    fn poll_ready(&mut self) -> Option<&T> {
+        if let CachedResult::Pending(task) = self {
+            if !task.is_finished() {
+                return None;
+            }
+            let CachedResult::Pending(task) = std::mem::replace(self, CachedResult::Taken) else {
+                unreachable!();
+            };
+            *self = CachedResult::Ready(futures_lite::future::block_on(task));
+        }
+        match self {
+            CachedResult::Ready(output) => Some(output),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), feature = "multi-threaded"))]
+mod tests {
+    use super::TaskCache;
+    use crate::{Task, TaskPool};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn same_key_reuses_the_cached_result() {
+        let pool = TaskPool::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let spawn = || {
+            let runs = runs.clone();
+            pool.spawn(async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                42
+            })
+        };
+
+        let mut cache = TaskCache::new(2);
+        while cache.get_or_spawn(7, spawn).is_none() {
+            std::thread::yield_now();
+        }
+        let not_spawned = || -> Task<i32> { panic!("the cached result should be reused") };
+        assert_eq!(cache.get_or_spawn(7, not_spawned), Some(&42));
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // Filling the cache evicts the least recently used key.
+        cache.get_or_spawn(1, spawn);
+        cache.get_or_spawn(7, not_spawned);
+        cache.get_or_spawn(2, spawn);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&7), Some(&42));
+        assert!(cache.invalidate(&7));
+        assert!(cache.get(&7).is_none());
+    }
+}
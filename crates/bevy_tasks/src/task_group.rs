@@ -0,0 +1,115 @@
+use crate::Task;
+
+/// A group of [`Task`]s tagged with a priority, so that they can be canceled or awaited by
+/// priority instead of one handle at a time.
+///
+/// [`TaskGroup::cancel_below`] and [`TaskGroup::join_above`] split the group at the same
+/// priority: the first handles the tasks with a lower priority, the second the rest.
+///
+/// Like a [`Task`], dropping the group cancels every task still in it.
+#[derive(Debug)]
+#[must_use = "Tasks are canceled when dropped, use `.detach()` to run them in the background."]
+pub struct TaskGroup<T> {
+    tasks: Vec<(u32, Task<T>)>,
+}
+
+impl<T> Default for TaskGroup<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TaskGroup<T> {
+    /// Creates an empty group.
+    pub fn new() -> Self {
+        Self { tasks: Vec::new() }
+    }
+
+    /// Adds `task` to the group with the given `priority`.
+    pub fn push(&mut self, priority: u32, task: Task<T>) {
+        self.tasks.push((priority, task));
+    }
+
+    /// The number of tasks in the group.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if the group has no tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Returns an iterator over the priorities of the tasks in the group, in the order they were
+    /// added.
+    pub fn priorities(&self) -> impl Iterator<Item = u32> + '_ {
+        self.tasks.iter().map(|(priority, _)| *priority)
+    }
+
+    /// Removes the tasks with a priority lower than `priority` from the group, then cancels them
+    /// and waits for them to stop running. See [`Task::cancel`].
+    ///
+    /// Returns the outputs of the canceled tasks that completed just before they got canceled.
+    pub async fn cancel_below(&mut self, priority: u32) -> Vec<T> {
+        let mut outputs = Vec::new();
+        for task in self.take_tasks(|task_priority| task_priority < priority) {
+            outputs.extend(task.cancel().await);
+        }
+        outputs
+    }
+
+    /// Removes the tasks with a priority of at least `priority` from the group and waits for them
+    /// to complete.
+    ///
+    /// Returns their outputs in the order the tasks were added.
+    pub async fn join_above(&mut self, priority: u32) -> Vec<T> {
+        let mut outputs = Vec::new();
+        for task in self.take_tasks(|task_priority| task_priority >= priority) {
+            outputs.push(task.await);
+        }
+        outputs
+    }
+
+    /// Detaches every task in the group to let them keep running in the background. See
+    /// [`Task::detach`].
+    pub fn detach(self) {
+        for (_, task) in self.tasks {
+            task.detach();
+        }
+    }
+
+    fn take_tasks(&mut self, mut take: impl FnMut(u32) -> bool) -> Vec<Task<T>> {
+        let (taken, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.tasks)
+            .into_iter()
+            .partition(|(priority, _)| take(*priority));
+        self.tasks = kept;
+        taken.into_iter().map(|(_, task)| task).collect()
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), feature = "multi-threaded"))]
+mod tests {
+    use super::TaskGroup;
+    use crate::{block_on, TaskPool};
+    use futures_lite::future;
+
+    #[test]
+    fn cancel_below_only_cancels_lower_priorities() {
+        let pool = TaskPool::new();
+        let mut group = TaskGroup::new();
+        for (priority, value) in [(1, 10), (5, 50), (0, 0), (9, 90)] {
+            if priority < 5 {
+                // Never completes, so it can only leave the group by being canceled.
+                group.push(priority, pool.spawn(future::pending::<i32>()));
+            } else {
+                group.push(priority, pool.spawn(async move { value }));
+            }
+        }
+
+        assert!(block_on(group.cancel_below(5)).is_empty());
+        assert_eq!(group.priorities().collect::<Vec<_>>(), [5, 9]);
+
+        assert_eq!(block_on(group.join_above(5)), [50, 90]);
+        assert!(group.is_empty());
+    }
+}
@@ -9,7 +9,7 @@ use smol_str::SmolStr;
 #[cfg(feature = "serialize")]
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 
-use crate::WindowTheme;
+use crate::{MonitorSelection, WindowTheme};
 
 /// A window event that is sent whenever a window's logical size has changed.
 #[derive(Event, Debug, Clone, PartialEq, Reflect)]
@@ -54,6 +54,24 @@ pub struct WindowCreated {
     pub window: Entity,
 }
 
+/// An event that is sent when the windowing backend fails to create a window.
+///
+/// The [`crate::Window`] component is left on the entity. To try again, for example with a
+/// smaller size, change the window and re-insert the component.
+#[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowCreationFailed {
+    /// Window that could not be created.
+    pub window: Entity,
+    /// The error reported by the windowing backend.
+    pub reason: String,
+}
+
 /// An event that is sent whenever the operating systems requests that a window
 /// be closed. This will be sent when the close button of the window is pressed.
 ///
@@ -76,6 +94,23 @@ pub struct WindowCloseRequested {
     pub window: Entity,
 }
 
+/// An event that is sent when [`close_when_requested`](crate::close_when_requested) marks a
+/// window as [`ClosingWindow`](crate::ClosingWindow).
+///
+/// The window is despawned during the next frame, unless [`KeepOpen`](crate::KeepOpen) is
+/// inserted on it during this frame.
+#[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowClosing {
+    /// Window that is closing.
+    pub window: Entity,
+}
+
 /// An event that is sent whenever a window is closed. This will be sent when
 /// the window entity loses its [`Window`](crate::window::Window) component or is despawned.
 #[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
@@ -347,6 +382,33 @@ pub struct WindowMoved {
     pub position: IVec2,
 }
 
+/// An event that moves a window onto another monitor.
+///
+/// The windowing backend repositions the window by setting its [`crate::Window::position`] to
+/// [`WindowPosition::At`](crate::WindowPosition::At). The window keeps its size and mode, so use
+/// the fullscreen [`WindowMode`](crate::WindowMode)s to fill a monitor instead.
+///
+/// If `monitor` doesn't reference a connected monitor, a warning is logged and the window
+/// isn't moved.
+#[derive(Event, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct MoveWindowToMonitor {
+    /// Window to move.
+    pub window: Entity,
+    /// The monitor to move the window to.
+    pub monitor: MonitorSelection,
+    /// The position of the window relative to the top-left corner of the monitor, in physical
+    /// pixels. The window is centered on the monitor if this is `None`.
+    ///
+    /// The position is clamped so that the window stays on the monitor if it fits.
+    pub offset: Option<IVec2>,
+}
+
 /// An event sent when the system theme changes for a window.
 ///
 /// This event is only sent when the window is relying on the system theme to control its appearance.
@@ -365,6 +427,30 @@ pub struct WindowThemeChanged {
     pub theme: WindowTheme,
 }
 
+/// An event that is sent when every window of the application has become occluded, so that
+/// audio and other background work can be paused.
+///
+/// It is only sent if `WinitSettings::background_when_occluded` is enabled, and is followed by an
+/// [`AppForegrounded`] event once any window becomes visible again.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AppBackgrounded;
+
+/// An event that is sent when a window becomes visible again after an [`AppBackgrounded`] event.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AppForegrounded;
+
 /// Application lifetime events
 #[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Debug, PartialEq)]
@@ -0,0 +1,131 @@
+use bevy_ecs::prelude::*;
+use bevy_math::{IRect, IVec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use std::path::PathBuf;
+#[cfg(feature = "serialize")]
+use std::{fs, io, path::Path};
+
+/// Remembers the size, position and maximized state of a [`Window`](crate::Window) across runs.
+///
+/// When a window with this component is created, the windowing backend restores the
+/// [`WindowGeometry`] saved at [`path`](Self::path), unless the file doesn't exist or the saved
+/// position isn't on any connected monitor. The geometry is saved again whenever the window is
+/// moved or resized, once it stays still for a moment, and when it is asked to close.
+///
+/// The geometry is stored in the RON format, so saving and restoring it requires the `serialize`
+/// feature. Without it, this component has no effect.
+///
+/// ```no_run
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_window::{PersistentWindowGeometry, Window};
+/// fn spawn_editor_window(mut commands: Commands) {
+///     commands.spawn((
+///         Window::default(),
+///         PersistentWindowGeometry::new("editor_window.ron"),
+///     ));
+/// }
+/// ```
+#[derive(Component, Default, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct PersistentWindowGeometry {
+    /// The file the geometry is saved to and restored from.
+    pub path: PathBuf,
+}
+
+impl PersistentWindowGeometry {
+    /// Creates a new [`PersistentWindowGeometry`] that saves to `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+/// The geometry of a window saved by [`PersistentWindowGeometry`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowGeometry {
+    /// The position of the top-left corner of the window, in physical pixels.
+    pub position: IVec2,
+    /// The logical width of the window.
+    pub width: f32,
+    /// The logical height of the window.
+    pub height: f32,
+    /// Whether the window is maximized.
+    pub maximized: bool,
+}
+
+impl WindowGeometry {
+    /// Reads a geometry previously written with [`WindowGeometry::save`].
+    #[cfg(feature = "serialize")]
+    pub fn load(path: &Path) -> io::Result<Self> {
+        ron::from_str(&fs::read_to_string(path)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Writes this geometry to `path`, replacing any previous contents.
+    #[cfg(feature = "serialize")]
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let geometry = ron::ser::to_string_pretty(self, Default::default())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        fs::write(path, geometry)
+    }
+
+    /// Returns `true` if the top-left corner of the window lies on one of `monitors`, given as
+    /// rectangles in physical pixels.
+    ///
+    /// Geometry saved while a monitor that has since been disconnected was in use fails this check,
+    /// and shouldn't be restored since the window could end up out of reach.
+    pub fn is_on_monitor(&self, monitors: impl IntoIterator<Item = IRect>) -> bool {
+        monitors
+            .into_iter()
+            .any(|monitor| monitor.contains(self.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WindowGeometry;
+    use bevy_math::{IRect, IVec2};
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn geometry_round_trips_through_a_file() {
+        let geometry = WindowGeometry {
+            position: IVec2::new(-40, 120),
+            width: 1024.0,
+            height: 768.5,
+            maximized: true,
+        };
+        let path =
+            std::env::temp_dir().join(format!("bevy_window_geometry_{}.ron", std::process::id()));
+
+        geometry.save(&path).unwrap();
+        let loaded = WindowGeometry::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), geometry);
+
+        std::fs::write(&path, "(position: (1, 2), width: 3.0)").unwrap();
+        let loaded = WindowGeometry::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn off_screen_geometry_is_rejected() {
+        let monitors = [
+            IRect::new(0, 0, 1920, 1080),
+            IRect::new(1920, 0, 1920 + 2560, 1440),
+        ];
+        let mut geometry = WindowGeometry {
+            position: IVec2::new(2000, 100),
+            width: 800.0,
+            height: 600.0,
+            maximized: false,
+        };
+        assert!(geometry.is_on_monitor(monitors));
+
+        // Saved while a third monitor to the left was connected.
+        geometry.position = IVec2::new(-1500, 100);
+        assert!(!geometry.is_on_monitor(monitors));
+        assert!(!geometry.is_on_monitor([]));
+    }
+}
@@ -9,6 +9,7 @@ use bevy_a11y::Focus;
 
 mod cursor;
 mod event;
+mod geometry;
 mod raw_handle;
 mod system;
 mod window;
@@ -17,6 +18,7 @@ pub use crate::raw_handle::*;
 
 pub use cursor::*;
 pub use event::*;
+pub use geometry::*;
 pub use system::*;
 pub use window::*;
 
@@ -31,6 +33,7 @@ pub mod prelude {
 }
 
 use bevy_app::prelude::*;
+use bevy_ecs::entity::Entity;
 use std::path::PathBuf;
 
 impl Default for WindowPlugin {
@@ -38,6 +41,7 @@ impl Default for WindowPlugin {
         WindowPlugin {
             primary_window: Some(Window::default()),
             exit_condition: ExitCondition::OnAllClosed,
+            exit_condition_grace_frames: 0,
             close_when_requested: true,
         }
     }
@@ -66,8 +70,19 @@ pub struct WindowPlugin {
     ///
     /// [`ExitCondition::OnAllClosed`] will add [`exit_on_all_closed`] to [`Update`].
     /// [`ExitCondition::OnPrimaryClosed`] will add [`exit_on_primary_closed`] to [`Update`].
+    /// [`ExitCondition::OnWindowClosed`] will add [`exit_on_window_closed`] to [`Update`].
     pub exit_condition: ExitCondition,
 
+    /// The number of consecutive frames without any open window after which
+    /// [`ExitCondition::OnAllClosed`] exits the app.
+    ///
+    /// With `0`, the app exits in the frame the last window is closed. A higher value lets apps
+    /// close their last window and open a new one shortly after, for example during a mode
+    /// switch, without exiting.
+    ///
+    /// Defaults to `0`.
+    pub exit_condition_grace_frames: u32,
+
     /// Whether to close windows when they are requested to be closed (i.e.
     /// when the close button is pressed).
     ///
@@ -82,8 +97,10 @@ impl Plugin for WindowPlugin {
         // User convenience events
         app.add_event::<WindowResized>()
             .add_event::<WindowCreated>()
+            .add_event::<WindowCreationFailed>()
             .add_event::<WindowClosed>()
             .add_event::<WindowCloseRequested>()
+            .add_event::<WindowClosing>()
             .add_event::<WindowDestroyed>()
             .add_event::<RequestRedraw>()
             .add_event::<CursorMoved>()
@@ -97,8 +114,12 @@ impl Plugin for WindowPlugin {
             .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
+            .add_event::<MoveWindowToMonitor>()
             .add_event::<WindowThemeChanged>()
-            .add_event::<ApplicationLifetime>();
+            .add_event::<ApplicationLifetime>()
+            .add_event::<AppBackgrounded>()
+            .add_event::<AppForegrounded>()
+            .init_resource::<CursorWindow>();
 
         if let Some(primary_window) = &self.primary_window {
             let initial_focus = app
@@ -116,11 +137,17 @@ impl Plugin for WindowPlugin {
                 app.add_systems(PostUpdate, exit_on_primary_closed);
             }
             ExitCondition::OnAllClosed => {
-                app.add_systems(PostUpdate, exit_on_all_closed);
+                app.insert_resource(ExitConditionGrace::new(self.exit_condition_grace_frames))
+                    .add_systems(PostUpdate, exit_on_all_closed);
+            }
+            ExitCondition::OnWindowClosed(window) => {
+                app.add_systems(PostUpdate, exit_on_window_closed(window));
             }
             ExitCondition::DontExit => {}
         }
 
+        app.add_systems(Update, update_exclusive_input);
+
         if self.close_when_requested {
             // Need to run before `exit_on_*` systems
             app.add_systems(Update, close_when_requested);
@@ -130,7 +157,9 @@ impl Plugin for WindowPlugin {
         app.register_type::<WindowResized>()
             .register_type::<RequestRedraw>()
             .register_type::<WindowCreated>()
+            .register_type::<WindowCreationFailed>()
             .register_type::<WindowCloseRequested>()
+            .register_type::<WindowClosing>()
             .register_type::<WindowClosed>()
             .register_type::<CursorMoved>()
             .register_type::<CursorEntered>()
@@ -142,13 +171,21 @@ impl Plugin for WindowPlugin {
             .register_type::<WindowBackendScaleFactorChanged>()
             .register_type::<FileDragAndDrop>()
             .register_type::<WindowMoved>()
+            .register_type::<MoveWindowToMonitor>()
             .register_type::<WindowThemeChanged>()
-            .register_type::<ApplicationLifetime>();
+            .register_type::<ApplicationLifetime>()
+            .register_type::<AppBackgrounded>()
+            .register_type::<AppForegrounded>();
 
         // Register window descriptor and related types
         app.register_type::<Window>()
             .register_type::<PrimaryWindow>()
+            .register_type::<CursorWindow>()
+            .register_type::<ClosingWindow>()
+            .register_type::<KeepOpen>()
             .register_type::<Cursor>()
+            .register_type::<ExclusiveInput>()
+            .register_type::<PersistentWindowGeometry>()
             .register_type::<CursorIcon>()
             .register_type::<CursorGrabMode>()
             .register_type::<CompositeAlphaMode>()
@@ -179,6 +216,14 @@ pub enum ExitCondition {
     ///
     /// The plugin will add [`exit_on_all_closed`] to [`Update`].
     OnAllClosed,
+    /// Close application when the given window is closed
+    ///
+    /// This is useful when the main window of the application isn't the primary one. The window
+    /// entity has to be spawned before the [`WindowPlugin`] is added, or reserved with
+    /// [`World::spawn_empty`](bevy_ecs::world::World::spawn_empty).
+    ///
+    /// The plugin will add [`exit_on_window_closed`] to [`Update`].
+    OnWindowClosed(Entity),
     /// Keep application running headless even after closing all windows
     ///
     /// If selecting this, ensure that you send the [`bevy_app::AppExit`]
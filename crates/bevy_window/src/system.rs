@@ -1,7 +1,10 @@
-use crate::{PrimaryWindow, Window, WindowCloseRequested};
+use crate::{
+    ClosingWindow, CursorGrabMode, ExclusiveInput, KeepOpen, PrimaryWindow, Window,
+    WindowCloseRequested, WindowClosing,
+};
 
 use bevy_app::AppExit;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
 use bevy_input::{keyboard::KeyCode, ButtonInput};
 
 /// Exit the application when there are no open windows.
@@ -10,11 +13,54 @@ use bevy_input::{keyboard::KeyCode, ButtonInput};
 /// To disable this behavior, set `close_when_requested` (on the [`WindowPlugin`]) to `false`.
 /// Ensure that you read the caveats documented on that field if doing so.
 ///
+/// If the [`ExitConditionGrace`] resource exists, the application only exits once no windows were
+/// open for more than its `grace_frames` consecutive frames.
+///
 /// [`WindowPlugin`]: crate::WindowPlugin
-pub fn exit_on_all_closed(mut app_exit_events: EventWriter<AppExit>, windows: Query<&Window>) {
-    if windows.is_empty() {
-        bevy_utils::tracing::info!("No windows are open, exiting");
-        app_exit_events.send(AppExit);
+pub fn exit_on_all_closed(
+    mut app_exit_events: EventWriter<AppExit>,
+    windows: Query<&Window>,
+    grace: Option<ResMut<ExitConditionGrace>>,
+) {
+    match grace {
+        Some(mut grace) if windows.is_empty() => {
+            grace.frames_without_windows += 1;
+            if grace.frames_without_windows <= grace.grace_frames {
+                return;
+            }
+        }
+        Some(mut grace) => {
+            if grace.frames_without_windows != 0 {
+                grace.frames_without_windows = 0;
+            }
+            return;
+        }
+        None if !windows.is_empty() => return,
+        None => {}
+    }
+
+    bevy_utils::tracing::info!("No windows are open, exiting");
+    app_exit_events.send(AppExit);
+}
+
+/// Delays [`exit_on_all_closed`] until no windows were open for a number of consecutive frames.
+///
+/// Inserted by the [`WindowPlugin`](crate::WindowPlugin) from
+/// [`WindowPlugin::exit_condition_grace_frames`](crate::WindowPlugin::exit_condition_grace_frames).
+#[derive(Resource, Debug, Default)]
+pub struct ExitConditionGrace {
+    /// The number of consecutive frames without windows that don't exit the application.
+    pub grace_frames: u32,
+    frames_without_windows: u32,
+}
+
+impl ExitConditionGrace {
+    /// Creates an [`ExitConditionGrace`] that tolerates `grace_frames` frames without windows.
+    pub fn new(grace_frames: u32) -> Self {
+        Self {
+            grace_frames,
+            frames_without_windows: 0,
+        }
     }
 }
 
@@ -33,16 +79,63 @@ pub fn exit_on_primary_closed(
     }
 }
 
+/// Returns a system that exits the application when `window` has been closed
+///
+/// This system is added by the [`WindowPlugin`] with [`ExitCondition::OnWindowClosed`]. If
+/// `window` was already despawned when the system first runs, the application exits right away.
+///
+/// [`WindowPlugin`]: crate::WindowPlugin
+/// [`ExitCondition::OnWindowClosed`]: crate::ExitCondition::OnWindowClosed
+pub fn exit_on_window_closed(
+    window: Entity,
+) -> impl FnMut(EventWriter<AppExit>, Query<(), With<Window>>) {
+    move |mut app_exit_events, windows| {
+        if !windows.contains(window) {
+            bevy_utils::tracing::info!("Window {window:?} was closed, exiting");
+            app_exit_events.send(AppExit);
+        }
+    }
+}
+
 /// Close windows in response to [`WindowCloseRequested`] (e.g.  when the close button is pressed).
 ///
 /// This system is added by the [`WindowPlugin`] in the default configuration.
 /// To disable this behavior, set `close_when_requested` (on the [`WindowPlugin`]) to `false`.
 /// Ensure that you read the caveats documented on that field if doing so.
 ///
+/// Windows aren't despawned right away. They are first marked as [`ClosingWindow`] and a
+/// [`WindowClosing`] event is sent, then they are despawned the next time this system runs,
+/// unless [`KeepOpen`] was inserted on them in between.
+///
 /// [`WindowPlugin`]: crate::WindowPlugin
-pub fn close_when_requested(mut commands: Commands, mut closed: EventReader<WindowCloseRequested>) {
+pub fn close_when_requested(
+    mut commands: Commands,
+    mut closed: EventReader<WindowCloseRequested>,
+    closing: Query<(Entity, Has<KeepOpen>), With<ClosingWindow>>,
+    mut closing_events: EventWriter<WindowClosing>,
+) {
+    for (window, keep_open) in &closing {
+        if keep_open {
+            commands
+                .entity(window)
+                .remove::<(ClosingWindow, KeepOpen)>();
+        } else {
+            commands.entity(window).despawn();
+        }
+    }
+
     for event in closed.read() {
-        commands.entity(event.window).despawn();
+        // A repeated request for a window that is despawned above doesn't start another close.
+        if matches!(closing.get(event.window), Ok((_, false))) {
+            continue;
+        }
+        // The window may have been despawned since the close was requested.
+        if let Some(mut window) = commands.get_entity(event.window) {
+            window.insert(ClosingWindow);
+            closing_events.send(WindowClosing {
+                window: event.window,
+            });
+        }
     }
 }
 
@@ -64,3 +157,179 @@ pub fn close_on_esc(
         }
     }
 }
+
+/// Applies [`ExclusiveInput`] to windows, capturing the cursor of focused windows that have it
+/// and restoring the previous cursor settings when they lose focus or the component is removed.
+///
+/// This system is added by the [`WindowPlugin`].
+///
+/// [`WindowPlugin`]: crate::WindowPlugin
+pub fn update_exclusive_input(
+    mut windows: Query<(Entity, &mut Window, Has<ExclusiveInput>)>,
+    // The grab mode and visibility each captured window's cursor had before it was captured.
+    mut captured: Local<EntityHashMap<(CursorGrabMode, bool)>>,
+) {
+    for (entity, mut window, exclusive_input) in &mut windows {
+        let capture = exclusive_input && window.focused;
+        if capture && !captured.contains_key(&entity) {
+            captured.insert(entity, (window.cursor.grab_mode, window.cursor.visible));
+            window.cursor.grab_mode = CursorGrabMode::Locked;
+            window.cursor.visible = false;
+        } else if !capture {
+            if let Some((grab_mode, visible)) = captured.remove(&entity) {
+                window.cursor.grab_mode = grab_mode;
+                window.cursor.visible = visible;
+            }
+        }
+    }
+
+    // Forget about windows that have been closed while captured.
+    captured.retain(|entity, _| windows.contains(*entity));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exclusive_input_is_suspended_while_unfocused() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_exclusive_input);
+
+        let mut window = Window::default();
+        window.cursor.grab_mode = CursorGrabMode::Confined;
+        let window = world.spawn((window, ExclusiveInput)).id();
+
+        let cursor = |world: &World| world.get::<Window>(window).unwrap().cursor;
+
+        schedule.run(&mut world);
+        assert_eq!(cursor(&world).grab_mode, CursorGrabMode::Locked);
+        assert!(!cursor(&world).visible);
+
+        world.get_mut::<Window>(window).unwrap().focused = false;
+        schedule.run(&mut world);
+        assert_eq!(cursor(&world).grab_mode, CursorGrabMode::Confined);
+        assert!(cursor(&world).visible);
+
+        world.get_mut::<Window>(window).unwrap().focused = true;
+        schedule.run(&mut world);
+        assert_eq!(cursor(&world).grab_mode, CursorGrabMode::Locked);
+        assert!(!cursor(&world).visible);
+
+        world.entity_mut(window).remove::<ExclusiveInput>();
+        schedule.run(&mut world);
+        assert_eq!(cursor(&world).grab_mode, CursorGrabMode::Confined);
+        assert!(cursor(&world).visible);
+    }
+
+    #[test]
+    fn reopening_a_window_within_the_grace_period_does_not_exit() {
+        let mut world = World::new();
+        world.init_resource::<Events<AppExit>>();
+        world.insert_resource(ExitConditionGrace::new(2));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(exit_on_all_closed);
+        let exited = |world: &World| !world.resource::<Events<AppExit>>().is_empty();
+
+        let window = world.spawn(Window::default()).id();
+        schedule.run(&mut world);
+        world.despawn(window);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        world.spawn(Window::default());
+        schedule.run(&mut world);
+        assert!(!exited(&world));
+
+        // The count starts over once a window is open again.
+        world.clear_entities();
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert!(!exited(&world));
+        schedule.run(&mut world);
+        assert!(exited(&world));
+    }
+
+    fn request_close(world: &mut World, window: Entity) -> Schedule {
+        world.init_resource::<Events<WindowCloseRequested>>();
+        world.init_resource::<Events<WindowClosing>>();
+        world.send_event(WindowCloseRequested { window });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(close_when_requested);
+        schedule.run(world);
+        assert!(world.get::<ClosingWindow>(window).is_some());
+        assert!(!world.resource::<Events<WindowClosing>>().is_empty());
+        schedule
+    }
+
+    #[test]
+    fn vetoed_close_keeps_the_window_open() {
+        let mut world = World::new();
+        let window = world.spawn(Window::default()).id();
+        let mut schedule = request_close(&mut world, window);
+
+        world.entity_mut(window).insert(KeepOpen);
+        schedule.run(&mut world);
+        assert!(world.get::<Window>(window).is_some());
+        assert!(world.get::<ClosingWindow>(window).is_none());
+        assert!(world.get::<KeepOpen>(window).is_none());
+
+        schedule.run(&mut world);
+        assert!(world.get_entity(window).is_some());
+    }
+
+    #[test]
+    fn close_proceeds_without_veto() {
+        let mut world = World::new();
+        let window = world.spawn(Window::default()).id();
+        let mut schedule = request_close(&mut world, window);
+
+        // The window stays for the frame the close was requested in.
+        assert!(world.get_entity(window).is_some());
+        schedule.run(&mut world);
+        assert!(world.get_entity(window).is_none());
+    }
+
+    #[test]
+    fn repeated_close_request_despawns_once() {
+        let mut world = World::new();
+        let window = world.spawn(Window::default()).id();
+        let mut schedule = request_close(&mut world, window);
+
+        // The close button is clicked again while the window is closing.
+        world.send_event(WindowCloseRequested { window });
+        schedule.run(&mut world);
+        assert!(world.get_entity(window).is_none());
+
+        schedule.run(&mut world);
+        assert!(world.get_entity(window).is_none());
+    }
+
+    #[test]
+    fn closing_the_designated_window_exits() {
+        let mut world = World::new();
+        world.init_resource::<Events<AppExit>>();
+        let primary = world.spawn((Window::default(), PrimaryWindow)).id();
+        let main = world.spawn(Window::default()).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(exit_on_window_closed(main));
+        let exited = |world: &World| !world.resource::<Events<AppExit>>().is_empty();
+
+        schedule.run(&mut world);
+        world.despawn(primary);
+        schedule.run(&mut world);
+        assert!(!exited(&world));
+
+        world.despawn(main);
+        schedule.run(&mut world);
+        assert!(exited(&world));
+
+        // A window that no longer exists exits right away.
+        let mut world = World::new();
+        world.init_resource::<Events<AppExit>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(exit_on_window_closed(main));
+        schedule.run(&mut world);
+        assert!(exited(&world));
+    }
+}
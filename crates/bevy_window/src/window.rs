@@ -1,6 +1,6 @@
 use bevy_ecs::{
     entity::{Entity, EntityMapper, MapEntities},
-    prelude::{Component, ReflectComponent},
+    prelude::{Component, ReflectComponent, ReflectResource, Resource},
 };
 use bevy_math::{DVec2, IVec2, Vec2};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
@@ -23,6 +23,51 @@ use crate::CursorIcon;
 #[reflect(Component)]
 pub struct PrimaryWindow;
 
+/// Marker [`Component`] for a [`Window`] whose close was requested and that
+/// [`close_when_requested`](crate::close_when_requested) will despawn during the next frame.
+///
+/// A [`WindowClosing`](crate::WindowClosing) event is sent when this is inserted. Inserting
+/// [`KeepOpen`] on the window before then vetoes the close.
+#[derive(Default, Debug, Component, PartialEq, Eq, Copy, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ClosingWindow;
+
+/// Marker [`Component`] that vetoes the close of a [`ClosingWindow`], for example to ask the user
+/// about unsaved changes first.
+///
+/// It has to be inserted in the same frame the [`WindowClosing`](crate::WindowClosing) event is
+/// sent, and is removed along with [`ClosingWindow`] once the close was vetoed. To close the
+/// window later, send another [`WindowCloseRequested`](crate::WindowCloseRequested) or despawn it.
+#[derive(Default, Debug, Component, PartialEq, Eq, Copy, Clone, Reflect)]
+#[reflect(Component)]
+pub struct KeepOpen;
+
+/// The window the cursor is currently over, if any.
+///
+/// This is kept up to date by the windowing backend from the [`CursorEntered`](crate::CursorEntered)
+/// and [`CursorLeft`](crate::CursorLeft) events of every window. When windows overlap, the cursor
+/// is over the last window it entered.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Default)]
+pub struct CursorWindow(pub Option<Entity>);
+
+impl CursorWindow {
+    /// Records that the cursor entered `window`.
+    pub fn enter(&mut self, window: Entity) {
+        self.0 = Some(window);
+    }
+
+    /// Records that the cursor left `window`, or that `window` was destroyed.
+    ///
+    /// This does nothing if the cursor already entered another window, since some platforms
+    /// report entering the new window before leaving the old one.
+    pub fn leave(&mut self, window: Entity) {
+        if self.0 == Some(window) {
+            self.0 = None;
+        }
+    }
+}
+
 /// Reference to a [`Window`], whether it be a direct link to a specific entity or
 /// a more vague defaulting choice.
 #[repr(C)]
@@ -513,6 +558,25 @@ impl Default for Cursor {
     }
 }
 
+/// Captures the cursor of a [`Window`] for exclusive use by the app.
+///
+/// While a window with this component is focused, its cursor is locked and hidden, as is typical
+/// for first-person games. When the window loses focus, the cursor settings it had before are
+/// restored so the user can interact with other applications, and the cursor is captured again
+/// once the window regains focus. Removing the component restores the cursor for good.
+///
+/// The capture is applied by [`update_exclusive_input`](crate::update_exclusive_input), which
+/// owns [`Cursor::grab_mode`] and [`Cursor::visible`] while the cursor is captured.
+///
+/// ## Platform-specific
+///
+/// Raw mouse motion is always reported through [`MouseMotion`](bevy_input::mouse::MouseMotion),
+/// and none of the supported windowing backends allow capturing system keyboard shortcuts, so
+/// this only affects the cursor.
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct ExclusiveInput;
+
 /// Defines where a [`Window`] should be placed on the screen.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Reflect)]
 #[cfg_attr(
@@ -826,7 +890,8 @@ impl InternalWindowState {
 
 /// References a screen monitor.
 ///
-/// Used when centering a [`Window`] on a monitor.
+/// Used when centering a [`Window`] on a monitor, or when choosing the monitor a fullscreen
+/// [`WindowMode`] applies to.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[cfg_attr(
     feature = "serialize",
@@ -994,27 +1059,32 @@ pub enum WindowMode {
     /// size of the screen.
     ///
     /// When setting this, the window's physical size will be modified to match the size
-    /// of the current monitor resolution, and the logical size will follow based
+    /// of the selected monitor resolution, and the logical size will follow based
     /// on the scale factor, see [`WindowResolution`].
     ///
     /// Note: As this mode respects the scale factor provided by the operating system,
     /// the window's logical size may be different from its physical size.
     /// If you want to avoid that behavior, you can use the [`WindowResolution::set_scale_factor_override`] function
     /// or the [`WindowResolution::with_scale_factor_override`] builder method to set the scale factor to 1.0.
-    BorderlessFullscreen,
+    ///
+    /// The [`MonitorSelection`] chooses the monitor to go fullscreen on. If it doesn't match a
+    /// monitor, the window's current monitor is used instead.
+    BorderlessFullscreen(MonitorSelection),
     /// The window should be in "true"/"legacy" Fullscreen mode.
     ///
     /// When setting this, the operating system will be requested to use the
-    /// **closest** resolution available for the current monitor to match as
+    /// **closest** resolution available for the selected monitor to match as
     /// closely as possible the window's physical size.
     /// After that, the window's physical size will be modified to match
     /// that monitor resolution, and the logical size will follow based on the
     /// scale factor, see [`WindowResolution`].
-    SizedFullscreen,
+    ///
+    /// The monitor is chosen by the [`MonitorSelection`], as for [`WindowMode::BorderlessFullscreen`].
+    SizedFullscreen(MonitorSelection),
     /// The window should be in "true"/"legacy" Fullscreen mode.
     ///
     /// When setting this, the operating system will be requested to use the
-    /// **biggest** resolution available for the current monitor.
+    /// **biggest** resolution available for the selected monitor.
     /// After that, the window's physical size will be modified to match
     /// that monitor resolution, and the logical size will follow based on the
     /// scale factor, see [`WindowResolution`].
@@ -1023,7 +1093,9 @@ pub enum WindowMode {
     /// the window's logical size may be different from its physical size.
     /// If you want to avoid that behavior, you can use the [`WindowResolution::set_scale_factor_override`] function
     /// or the [`WindowResolution::with_scale_factor_override`] builder method to set the scale factor to 1.0.
-    Fullscreen,
+    ///
+    /// The monitor is chosen by the [`MonitorSelection`], as for [`WindowMode::BorderlessFullscreen`].
+    Fullscreen(MonitorSelection),
 }
 
 /// Specifies where a [`Window`] should appear relative to other overlapping windows (on top or under) .
@@ -1163,4 +1235,25 @@ mod tests {
         window.set_physical_cursor_position(Some(DVec2::new(400., 600.)));
         assert!(window.physical_cursor_position().is_none());
     }
+
+    #[test]
+    fn cursor_window_tracks_the_hovered_window() {
+        let (a, b) = (Entity::from_raw(0), Entity::from_raw(1));
+        let mut cursor_window = CursorWindow::default();
+
+        cursor_window.enter(a);
+        assert_eq!(cursor_window.0, Some(a));
+
+        // Moving onto an overlapping window may report entering it before leaving the other one.
+        cursor_window.enter(b);
+        cursor_window.leave(a);
+        assert_eq!(cursor_window.0, Some(b));
+
+        cursor_window.leave(b);
+        cursor_window.enter(a);
+        assert_eq!(cursor_window.0, Some(a));
+
+        cursor_window.leave(a);
+        assert_eq!(cursor_window.0, None);
+    }
 }
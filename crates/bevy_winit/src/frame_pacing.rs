@@ -0,0 +1,209 @@
+use bevy_ecs::system::Resource;
+use bevy_utils::{tracing::warn, Duration};
+use std::collections::VecDeque;
+use winit::event::{Event, StartCause};
+
+/// Measured timing of the updates run by the `winit` runner, for diagnosing stutter.
+///
+/// Unlike `Time`, which is advanced by the app itself, this reports when the runner actually
+/// called [`App::update`](bevy_app::App::update) and how long the call took. The values are
+/// recorded after each update, so during an update they describe the previous one.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct FramePacing {
+    /// How long the last call to [`App::update`](bevy_app::App::update) took.
+    pub last_update_duration: Duration,
+    /// The time between the start of the last update and the start of the one before it.
+    pub last_interval: Duration,
+    intervals: VecDeque<Duration>,
+}
+
+impl FramePacing {
+    /// The number of intervals [`FramePacing::average_interval`] is computed over.
+    pub const HISTORY_LEN: usize = 120;
+
+    /// Returns the average interval between updates over the last
+    /// [`HISTORY_LEN`](Self::HISTORY_LEN) updates, or [`Duration::ZERO`] before the first one.
+    pub fn average_interval(&self) -> Duration {
+        if self.intervals.is_empty() {
+            return Duration::ZERO;
+        }
+        self.intervals.iter().sum::<Duration>() / self.intervals.len() as u32
+    }
+
+    pub(crate) fn record(&mut self, interval: Duration, update_duration: Duration) {
+        if self.intervals.len() == Self::HISTORY_LEN {
+            self.intervals.pop_front();
+        }
+        self.intervals.push_back(interval);
+        self.last_interval = interval;
+        self.last_update_duration = update_duration;
+    }
+}
+
+/// Counts the [`RequestRedraw`](bevy_window::RequestRedraw) events read by the `winit` runner
+/// after each update, for finding systems that request redraws much more often than needed.
+///
+/// A single request is enough to run another update, so a high count usually means that a system
+/// requests a redraw for every entity it touches, or every frame without a reason.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RedrawRequestStats {
+    /// The number of redraws requested during the last update.
+    pub last_update: usize,
+    /// The number of consecutive updates, up to and including the last one, that requested a
+    /// redraw.
+    pub consecutive_updates: u32,
+    /// If `Some`, a warning is logged after each update that requested more redraws than this.
+    ///
+    /// Defaults to `None`.
+    pub warn_threshold: Option<usize>,
+}
+
+impl RedrawRequestStats {
+    /// Creates stats that log a warning after updates requesting more than `warn_threshold`
+    /// redraws.
+    pub fn with_warn_threshold(warn_threshold: usize) -> Self {
+        Self {
+            warn_threshold: Some(warn_threshold),
+            ..Default::default()
+        }
+    }
+
+    /// Records the number of redraws requested during an update, and returns `true` if it's
+    /// above the [`warn_threshold`](Self::warn_threshold).
+    pub(crate) fn record(&mut self, requests: usize) -> bool {
+        self.last_update = requests;
+        self.consecutive_updates = match requests {
+            0 => 0,
+            _ => self.consecutive_updates.saturating_add(1),
+        };
+
+        let storm = self
+            .warn_threshold
+            .is_some_and(|warn_threshold| requests > warn_threshold);
+        if storm {
+            warn!(
+                "{requests} redraws were requested during the last update, and redraws were requested for {} updates in a row",
+                self.consecutive_updates
+            );
+        }
+        storm
+    }
+}
+
+/// Counts how often the `winit` event loop woke up, and the events it woke up for, to find out
+/// what keeps an app busy when it should be idle.
+///
+/// This resource isn't added by default. The runner only updates it after it has been inserted,
+/// and the counts keep growing until they are reset, for example by inserting a default value
+/// again.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct WakeupStats {
+    /// The number of times the event loop started processing a new batch of events.
+    pub wakeups: u64,
+    /// The number of wakeups caused by a timer set with
+    /// [`ControlFlow::WaitUntil`](winit::event_loop::ControlFlow::WaitUntil) running out.
+    pub timer_wakeups: u64,
+    /// The number of window events received.
+    pub window_events: u64,
+    /// The number of device events received, like raw mouse motion.
+    pub device_events: u64,
+    /// The number of user events received, sent through
+    /// [`EventLoopProxyResource`](crate::EventLoopProxyResource) to wake the app up.
+    pub user_events: u64,
+}
+
+impl WakeupStats {
+    pub(crate) fn record(&mut self, event: &Event<()>) {
+        match event {
+            Event::NewEvents(cause) => {
+                self.wakeups += 1;
+                if matches!(cause, StartCause::ResumeTimeReached { .. }) {
+                    self.timer_wakeups += 1;
+                }
+            }
+            Event::WindowEvent { .. } => self.window_events += 1,
+            Event::DeviceEvent { .. } => self.device_events += 1,
+            Event::UserEvent(()) => self.user_events += 1,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FramePacing, RedrawRequestStats, WakeupStats};
+    use bevy_utils::{Duration, Instant};
+    use winit::{
+        event::{DeviceEvent, DeviceId, Event, StartCause, WindowEvent},
+        window::WindowId,
+    };
+
+    #[test]
+    fn average_interval_covers_recent_updates() {
+        let mut pacing = FramePacing::default();
+        assert_eq!(pacing.average_interval(), Duration::ZERO);
+
+        for i in 0..200 {
+            let interval = Duration::from_millis(if i % 2 == 0 { 10 } else { 20 });
+            pacing.record(interval, Duration::from_millis(4));
+        }
+
+        let average = pacing.average_interval();
+        assert!(average >= Duration::from_millis(10) && average <= Duration::from_millis(20));
+        assert_eq!(average, Duration::from_millis(15));
+        assert_eq!(pacing.last_interval, Duration::from_millis(20));
+        assert_eq!(pacing.last_update_duration, Duration::from_millis(4));
+        assert_eq!(pacing.intervals.len(), FramePacing::HISTORY_LEN);
+    }
+
+    #[test]
+    fn redraw_storms_are_detected() {
+        let mut stats = RedrawRequestStats::default();
+        assert!(!stats.record(1000));
+        assert_eq!(stats.last_update, 1000);
+
+        let mut stats = RedrawRequestStats::with_warn_threshold(8);
+        assert!(!stats.record(1));
+        assert!(!stats.record(8));
+        assert!(stats.record(50));
+        assert_eq!(stats.consecutive_updates, 3);
+
+        assert!(!stats.record(0));
+        assert_eq!(stats.consecutive_updates, 0);
+    }
+
+    #[test]
+    fn wakeups_are_counted_by_cause() {
+        // SAFETY: the dummy ids are never passed to winit.
+        let window_id = unsafe { WindowId::dummy() };
+        // SAFETY: as above.
+        let device_id = unsafe { DeviceId::dummy() };
+
+        let mut stats = WakeupStats::default();
+        let now = Instant::now();
+        stats.record(&Event::NewEvents(StartCause::ResumeTimeReached {
+            start: now,
+            requested_resume: now,
+        }));
+        stats.record(&Event::NewEvents(StartCause::WaitCancelled {
+            start: now,
+            requested_resume: None,
+        }));
+        stats.record(&Event::WindowEvent {
+            window_id,
+            event: WindowEvent::Focused(true),
+        });
+        stats.record(&Event::DeviceEvent {
+            device_id,
+            event: DeviceEvent::Added,
+        });
+        stats.record(&Event::UserEvent(()));
+        stats.record(&Event::AboutToWait);
+
+        assert_eq!(stats.wakeups, 2);
+        assert_eq!(stats.timer_wakeups, 1);
+        assert_eq!(stats.window_events, 1);
+        assert_eq!(stats.device_events, 1);
+        assert_eq!(stats.user_events, 1);
+    }
+}
@@ -7,6 +7,7 @@
 
 pub mod accessibility;
 mod converters;
+mod frame_pacing;
 mod system;
 mod winit_config;
 mod winit_windows;
@@ -14,15 +15,24 @@ mod winit_windows;
 use approx::relative_eq;
 use bevy_a11y::AccessibilityRequested;
 use bevy_utils::{Duration, Instant};
-use system::{changed_windows, create_windows, despawn_windows, CachedWindow};
+pub use frame_pacing::*;
+use std::sync::Mutex;
+use system::{
+    changed_windows, create_windows, despawn_windows, disable_dpi_scaling,
+    move_windows_to_monitors, CachedWindow,
+};
 use winit::dpi::{LogicalSize, PhysicalSize};
 pub use winit_config::*;
 pub use winit_windows::*;
+#[cfg(feature = "serialize")]
+use {bevy_app::PreUpdate, system::save_window_geometry};
 
 use bevy_app::{App, AppExit, Last, Plugin, PluginsState};
+use bevy_ecs::entity::{EntityHashMap, EntityHashSet};
 use bevy_ecs::event::{Events, ManualEventReader};
 use bevy_ecs::prelude::*;
-use bevy_ecs::system::SystemState;
+use bevy_ecs::query::QueryFilter;
+use bevy_ecs::system::{SystemParamItem, SystemState};
 use bevy_input::{
     mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
     touchpad::{TouchpadMagnify, TouchpadRotate},
@@ -32,11 +42,11 @@ use bevy_math::{ivec2, DVec2, Vec2};
 use bevy_tasks::tick_global_task_pools_on_main_thread;
 use bevy_utils::tracing::{error, trace, warn};
 use bevy_window::{
-    exit_on_all_closed, ApplicationLifetime, CursorEntered, CursorLeft, CursorMoved,
-    FileDragAndDrop, Ime, ReceivedCharacter, RequestRedraw, Window,
-    WindowBackendScaleFactorChanged, WindowCloseRequested, WindowCreated, WindowDestroyed,
-    WindowFocused, WindowMoved, WindowOccluded, WindowResized, WindowScaleFactorChanged,
-    WindowThemeChanged,
+    exit_on_all_closed, AppBackgrounded, AppForegrounded, ApplicationLifetime, CursorEntered,
+    CursorLeft, CursorMoved, CursorWindow, FileDragAndDrop, Ime, PersistentWindowGeometry,
+    ReceivedCharacter, RequestRedraw, Window, WindowBackendScaleFactorChanged,
+    WindowCloseRequested, WindowCreated, WindowCreationFailed, WindowDestroyed, WindowFocused,
+    WindowMoved, WindowOccluded, WindowResized, WindowScaleFactorChanged, WindowThemeChanged,
 };
 #[cfg(target_os = "android")]
 use bevy_window::{PrimaryWindow, RawHandleWrapper};
@@ -46,7 +56,10 @@ pub use winit::platform::android::activity as android_activity;
 
 use winit::{
     event::{self, DeviceEvent, Event, WindowEvent},
-    event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopWindowTarget},
+    event_loop::{
+        ControlFlow, EventLoop, EventLoopBuilder, EventLoopClosed, EventLoopProxy,
+        EventLoopWindowTarget,
+    },
 };
 
 use crate::accessibility::{AccessKitAdapters, AccessKitPlugin, WinitActionHandlers};
@@ -117,17 +130,24 @@ impl Plugin for WinitPlugin {
 
         app.init_non_send_resource::<WinitWindows>()
             .init_resource::<WinitSettings>()
+            .init_resource::<FramePacing>()
+            .init_resource::<RedrawRequestStats>()
             .set_runner(winit_runner)
             .add_systems(
                 Last,
                 (
                     // `exit_on_all_closed` only checks if windows exist but doesn't access data,
                     // so we don't need to care about its ordering relative to `changed_windows`
+                    disable_dpi_scaling,
+                    move_windows_to_monitors,
                     changed_windows.ambiguous_with(exit_on_all_closed),
                     despawn_windows,
                 )
                     .chain(),
             );
+        // Runs before `close_when_requested` despawns the windows that are asked to close.
+        #[cfg(feature = "serialize")]
+        app.add_systems(PreUpdate, save_window_geometry);
 
         app.add_plugins(AccessKitPlugin);
 
@@ -147,8 +167,9 @@ impl Plugin for WinitPlugin {
             // so that we have a surface to use as a hint. This improves compatibility with `wgpu`
             // backends, especially WASM/WebGL2.
             let mut create_window = SystemState::<CreateWindowParams>::from_world(&mut app.world);
-            create_windows(&event_loop, create_window.get_mut(&mut app.world));
-            create_window.apply(&mut app.world);
+            create_pending_windows(&mut app.world, &mut create_window, |params| {
+                create_windows(&event_loop, params);
+            });
         }
 
         // `winit`'s windows are bound to the event loop that created them, so the event loop must
@@ -158,14 +179,138 @@ impl Plugin for WinitPlugin {
 }
 
 trait AppSendEvent {
-    fn send_event<E: bevy_ecs::event::Event>(&mut self, event: E);
+    /// Sends `event`, logging it first if `log_input_events` is set, see
+    /// [`WinitSettings::log_input_events`].
+    fn send_event<E: bevy_ecs::event::Event + std::fmt::Debug>(
+        &mut self,
+        event: E,
+        log_input_events: bool,
+    );
 }
 impl AppSendEvent for App {
-    fn send_event<E: bevy_ecs::event::Event>(&mut self, event: E) {
+    fn send_event<E: bevy_ecs::event::Event + std::fmt::Debug>(
+        &mut self,
+        event: E,
+        log_input_events: bool,
+    ) {
+        if log_input_events {
+            trace!("Forwarding {event:?}");
+        }
         self.world.send_event(event);
     }
 }
 
+/// A [`Resource`] that wakes the `winit` event loop from any system or thread.
+///
+/// Every wake up requests a redraw, so the app runs an update even while
+/// [`UpdateMode::Reactive`] or [`UpdateMode::ReactiveLowPower`] is waiting. Use
+/// [`EventLoopProxyResource::proxy`] to get a proxy that can be moved into another thread.
+#[derive(Resource)]
+pub struct EventLoopProxyResource(Mutex<EventLoopProxy<()>>);
+
+impl EventLoopProxyResource {
+    fn new(event_loop: &EventLoop<()>) -> Self {
+        Self(Mutex::new(event_loop.create_proxy()))
+    }
+
+    /// Wakes the event loop and requests a redraw.
+    ///
+    /// Returns an error if the event loop no longer exists.
+    pub fn wake(&self) -> Result<(), EventLoopClosed<()>> {
+        self.0.lock().unwrap().send_event(())
+    }
+
+    /// Returns a new [`EventLoopProxy`] for the event loop, which can be moved into another
+    /// thread. Sending `()` through it has the same effect as [`EventLoopProxyResource::wake`].
+    pub fn proxy(&self) -> EventLoopProxy<()> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// A [`Resource`] that decides which `winit` [`WindowEvent`]s are turned into Bevy events.
+///
+/// The filter sees every window event before Bevy translates it. When it returns `false`, the
+/// event is skipped: no Bevy events are sent for it and the [`Window`] component isn't updated.
+/// AccessKit still receives every event. This can be used to handle some events entirely outside
+/// of Bevy, for example to implement a custom title bar.
+///
+/// ```
+/// # use bevy_winit::RawWindowEventFilter;
+/// # use winit::event::WindowEvent;
+/// // Handle scrolling without Bevy.
+/// let filter = RawWindowEventFilter::new(|event| !matches!(event, WindowEvent::MouseWheel { .. }));
+/// ```
+#[derive(Resource)]
+pub struct RawWindowEventFilter(Box<dyn FnMut(&WindowEvent) -> bool + Send + Sync>);
+
+impl RawWindowEventFilter {
+    /// Creates a filter that forwards the window events for which `filter` returns `true`.
+    pub fn new(filter: impl FnMut(&WindowEvent) -> bool + Send + Sync + 'static) -> Self {
+        Self(Box::new(filter))
+    }
+
+    /// Returns `true` if `event` should be translated into Bevy events.
+    pub fn forward(&mut self, event: &WindowEvent) -> bool {
+        (self.0)(event)
+    }
+}
+
+/// What the runner knows after an update when a [`ControlFlowPolicy`] decides the next one.
+pub struct ControlFlowContext<'a> {
+    /// The world of the app, after the update.
+    pub world: &'a World,
+    /// The [`UpdateMode`] that satisfies every window.
+    pub update_mode: UpdateMode,
+    /// The time the last update started.
+    pub last_update: Instant,
+    /// The [`ControlFlow`] chosen from the `update_mode`.
+    pub control_flow: ControlFlow,
+}
+
+/// A [`Resource`] that overrides when the next update runs.
+///
+/// After every update, the runner picks a [`ControlFlow`] from the [`UpdateMode`] of
+/// [`WinitSettings`] and the windows, then passes it to the policy in a [`ControlFlowContext`].
+/// The [`ControlFlow`] returned by the policy takes precedence:
+/// - [`ControlFlow::Poll`] runs the next update as soon as possible, like
+///   [`UpdateMode::Continuous`].
+/// - [`ControlFlow::WaitUntil`] runs the next update at the given time at the latest.
+/// - [`ControlFlow::Wait`] waits for the next event.
+///
+/// This can be used for pacing strategies that the update modes don't cover, for example
+/// based on the load reported by the app.
+///
+/// ```
+/// # use bevy_winit::ControlFlowPolicy;
+/// # use winit::event_loop::ControlFlow;
+/// // Keep updating while a loading screen is shown.
+/// # #[derive(bevy_ecs::system::Resource)]
+/// # struct Loading;
+/// let policy = ControlFlowPolicy::new(|context| {
+///     if context.world.contains_resource::<Loading>() {
+///         ControlFlow::Poll
+///     } else {
+///         context.control_flow
+///     }
+/// });
+/// ```
+#[derive(Resource)]
+pub struct ControlFlowPolicy(Box<dyn Fn(&ControlFlowContext) -> ControlFlow + Send + Sync>);
+
+impl ControlFlowPolicy {
+    /// Creates a policy that uses the [`ControlFlow`] returned by `policy`.
+    pub fn new(
+        policy: impl Fn(&ControlFlowContext) -> ControlFlow + Send + Sync + 'static,
+    ) -> Self {
+        Self(Box::new(policy))
+    }
+
+    /// Returns the [`ControlFlow`] to use until the next update.
+    pub fn decide(&self, context: &ControlFlowContext) -> ControlFlow {
+        (self.0)(context)
+    }
+}
+
 /// Persistent state that is used to run the [`App`] according to the current
 /// [`UpdateMode`].
 struct WinitAppRunnerState {
@@ -185,6 +330,13 @@ struct WinitAppRunnerState {
     scheduled_update: Option<Instant>,
     /// Number of "forced" updates to trigger on application start
     startup_forced_updates: u32,
+    /// Resizes waiting to be sent before the next update, see
+    /// [`WinitSettings::resize_coalescing_threshold`].
+    pending_resizes: PendingResizes,
+    /// Windows with uncommitted IME composition text.
+    ime_preedits: ImePreedits,
+    /// Which windows are occluded, see [`WinitSettings::background_when_occluded`].
+    occlusion: Occlusion,
 }
 
 impl WinitAppRunnerState {
@@ -196,6 +348,99 @@ impl WinitAppRunnerState {
     }
 }
 
+/// The latest [`WindowResized`] event of each window that hasn't been sent yet.
+#[derive(Default)]
+struct PendingResizes(EntityHashMap<WindowResized>);
+
+impl PendingResizes {
+    /// Holds back `resized` until the next update, replacing any earlier pending resize of the
+    /// same window.
+    ///
+    /// If the size changed by at least `threshold` from `previous_size`, the pending resize is
+    /// dropped and `resized` is returned to be sent right away instead.
+    fn push(
+        &mut self,
+        resized: WindowResized,
+        previous_size: Vec2,
+        threshold: f32,
+    ) -> Option<WindowResized> {
+        let size = Vec2::new(resized.width, resized.height);
+        if (size - previous_size).abs().max_element() >= threshold {
+            self.0.remove(&resized.window);
+            return Some(resized);
+        }
+        self.0.insert(resized.window, resized);
+        None
+    }
+
+    /// Returns the pending resizes, leaving none behind.
+    fn drain(&mut self) -> impl Iterator<Item = WindowResized> + '_ {
+        self.0.drain().map(|(_, resized)| resized)
+    }
+}
+
+/// Tracks which windows have IME composition text that hasn't been committed or cleared.
+#[derive(Default)]
+struct ImePreedits(EntityHashSet);
+
+impl ImePreedits {
+    fn track(&mut self, ime: &Ime) {
+        match ime {
+            Ime::Preedit { window, value, .. } if !value.is_empty() => {
+                self.0.insert(*window);
+            }
+            Ime::Preedit { window, .. } | Ime::Commit { window, .. } | Ime::Disabled { window } => {
+                self.0.remove(window);
+            }
+            Ime::Enabled { .. } => {}
+        }
+    }
+
+    /// Returns an event clearing the composition text of `window`, if it has any.
+    fn clear(&mut self, window: Entity) -> Option<Ime> {
+        self.0.remove(&window).then(|| Ime::Preedit {
+            window,
+            value: String::new(),
+            cursor: None,
+        })
+    }
+}
+
+/// Tracks whether all windows are occluded.
+#[derive(Default)]
+struct Occlusion {
+    occluded: EntityHashSet,
+    backgrounded: bool,
+}
+
+impl Occlusion {
+    /// Records the occlusion of `window`, given all currently open `windows`.
+    ///
+    /// Returns `Some(true)` when this leaves every window occluded, and `Some(false)` when a
+    /// window becomes visible after that. Otherwise returns `None`.
+    fn set_occluded(
+        &mut self,
+        window: Entity,
+        occluded: bool,
+        windows: impl IntoIterator<Item = Entity>,
+    ) -> Option<bool> {
+        if occluded {
+            self.occluded.insert(window);
+        } else {
+            self.occluded.remove(&window);
+        }
+
+        let mut windows = windows.into_iter().peekable();
+        let all_occluded =
+            windows.peek().is_some() && windows.all(|window| self.occluded.contains(&window));
+        if all_occluded == self.backgrounded {
+            return None;
+        }
+        self.backgrounded = all_occluded;
+        Some(all_occluded)
+    }
+}
+
 #[derive(PartialEq, Eq)]
 enum ActiveState {
     NotYetStarted,
@@ -214,8 +459,8 @@ impl ActiveState {
     }
 }
 
-impl Default for WinitAppRunnerState {
-    fn default() -> Self {
+impl WinitAppRunnerState {
+    fn new(startup_forced_updates: u32) -> Self {
         Self {
             active: ActiveState::NotYetStarted,
             window_event_received: false,
@@ -224,16 +469,50 @@ impl Default for WinitAppRunnerState {
             wait_elapsed: false,
             last_update: Instant::now(),
             scheduled_update: None,
-            // 3 seems to be enough, 5 is a safe margin
-            startup_forced_updates: 5,
+            startup_forced_updates,
+            pending_resizes: PendingResizes::default(),
+            ime_preedits: ImePreedits::default(),
+            occlusion: Occlusion::default(),
         }
     }
+
+    /// Returns `true` and uses up one of the forced startup updates, if any are left.
+    fn take_startup_forced_update(&mut self) -> bool {
+        if self.startup_forced_updates == 0 {
+            return false;
+        }
+        self.startup_forced_updates -= 1;
+        true
+    }
 }
 
+type UpdateModeParams<'w, 's> = (
+    Res<'w, WinitSettings>,
+    Query<'w, 's, (&'static Window, Option<&'static WindowUpdateMode>)>,
+);
+
+type WindowEventParams<'w, 's> = (
+    EventWriter<'w, WindowResized>,
+    NonSend<'w, WinitWindows>,
+    Query<'w, 's, (&'static mut Window, &'static mut CachedWindow)>,
+    NonSend<'w, AccessKitAdapters>,
+);
+
 type CreateWindowParams<'w, 's, F = ()> = (
+    Res<'w, WinitSettings>,
     Commands<'w, 's>,
-    Query<'w, 's, (Entity, &'static mut Window), F>,
+    Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static mut Window,
+            Option<&'static PersistentWindowGeometry>,
+        ),
+        F,
+    >,
     EventWriter<'w, WindowCreated>,
+    EventWriter<'w, WindowCreationFailed>,
     NonSendMut<'w, WinitWindows>,
     NonSendMut<'w, AccessKitAdapters>,
     ResMut<'w, WinitActionHandlers>,
@@ -256,23 +535,19 @@ pub fn winit_runner(mut app: App) {
         .unwrap();
 
     app.world
-        .insert_non_send_resource(event_loop.create_proxy());
+        .insert_resource(EventLoopProxyResource::new(&event_loop));
 
-    let mut runner_state = WinitAppRunnerState::default();
+    let mut runner_state =
+        WinitAppRunnerState::new(app.world.resource::<WinitSettings>().startup_forced_updates);
 
     // prepare structures to access data in the world
     let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
     let mut redraw_event_reader = ManualEventReader::<RequestRedraw>::default();
 
-    let mut focused_windows_state: SystemState<(Res<WinitSettings>, Query<&Window>)> =
-        SystemState::new(&mut app.world);
+    let mut focused_windows_state: SystemState<UpdateModeParams> = SystemState::new(&mut app.world);
 
-    let mut event_writer_system_state: SystemState<(
-        EventWriter<WindowResized>,
-        NonSend<WinitWindows>,
-        Query<(&mut Window, &mut CachedWindow)>,
-        NonSend<AccessKitAdapters>,
-    )> = SystemState::new(&mut app.world);
+    let mut event_writer_system_state: SystemState<WindowEventParams> =
+        SystemState::new(&mut app.world);
 
     let mut create_window =
         SystemState::<CreateWindowParams<Added<Window>>>::from_world(&mut app.world);
@@ -304,13 +579,8 @@ fn handle_winit_event(
     app_exit_event_reader: &mut ManualEventReader<AppExit>,
     runner_state: &mut WinitAppRunnerState,
     create_window: &mut SystemState<CreateWindowParams<Added<Window>>>,
-    event_writer_system_state: &mut SystemState<(
-        EventWriter<WindowResized>,
-        NonSend<WinitWindows>,
-        Query<(&mut Window, &mut CachedWindow)>,
-        NonSend<AccessKitAdapters>,
-    )>,
-    focused_windows_state: &mut SystemState<(Res<WinitSettings>, Query<&Window>)>,
+    event_writer_system_state: &mut SystemState<WindowEventParams>,
+    focused_windows_state: &mut SystemState<UpdateModeParams>,
     redraw_event_reader: &mut ManualEventReader<RequestRedraw>,
     event: Event<()>,
     event_loop: &EventLoopWindowTarget<()>,
@@ -336,11 +606,19 @@ fn handle_winit_event(
         }
     }
 
+    if let Some(mut wakeup_stats) = app.world.get_resource_mut::<WakeupStats>() {
+        wakeup_stats.record(&event);
+    }
+
+    let log_input_events = app
+        .world
+        .get_resource::<WinitSettings>()
+        .is_some_and(|settings| settings.log_input_events);
+
     match event {
         Event::AboutToWait => {
             let (config, windows) = focused_windows_state.get(&app.world);
-            let focused = windows.iter().any(|window| window.focused);
-            let mut should_update = match config.update_mode(focused) {
+            let mut should_update = match config.effective_update_mode(windows.iter()) {
                 UpdateMode::Continuous => {
                     runner_state.redraw_requested
                         || runner_state.window_event_received
@@ -360,8 +638,7 @@ fn handle_winit_event(
             };
 
             // Ensure that an update is triggered on the first iterations for app initialization
-            if runner_state.startup_forced_updates > 0 {
-                runner_state.startup_forced_updates -= 1;
+            if runner_state.take_startup_forced_update() {
                 should_update = true;
             }
 
@@ -371,7 +648,7 @@ fn handle_winit_event(
             }
 
             if should_update {
-                let visible = windows.iter().any(|window| window.visible);
+                let visible = windows.iter().any(|(window, _)| window.visible);
                 let (_, winit_windows, _, _) = event_writer_system_state.get_mut(&mut app.world);
                 if visible && runner_state.active != ActiveState::WillSuspend {
                     for window in winit_windows.windows.values() {
@@ -405,216 +682,41 @@ fn handle_winit_event(
         Event::WindowEvent {
             event, window_id, ..
         } => {
-            let (mut window_resized, winit_windows, mut windows, access_kit_adapters) =
-                event_writer_system_state.get_mut(&mut app.world);
-
+            let (_, winit_windows, _, _) = event_writer_system_state.get_mut(&mut app.world);
             let Some(window) = winit_windows.get_window_entity(window_id) else {
                 warn!("Skipped event {event:?} for unknown winit Window Id {window_id:?}");
                 return;
             };
 
-            let Ok((mut win, _)) = windows.get_mut(window) else {
-                warn!("Window {window:?} is missing `Window` component, skipping event {event:?}");
-                return;
-            };
-
-            // Allow AccessKit to respond to `WindowEvent`s before they reach
-            // the engine.
-            if let Some(adapter) = access_kit_adapters.get(&window) {
-                if let Some(winit_window) = winit_windows.get_window(window) {
-                    adapter.process_event(winit_window, &event);
-                }
-            }
-
-            runner_state.window_event_received = true;
-
-            match event {
-                WindowEvent::Resized(size) => {
-                    react_to_resize(&mut win, size, &mut window_resized, window);
-                }
-                WindowEvent::CloseRequested => app.send_event(WindowCloseRequested { window }),
-                WindowEvent::KeyboardInput { ref event, .. } => {
-                    if event.state.is_pressed() {
-                        if let Some(char) = &event.text {
-                            let char = char.clone();
-                            app.send_event(ReceivedCharacter { window, char });
-                        }
-                    }
-                    app.send_event(converters::convert_keyboard_input(event, window));
-                }
-                WindowEvent::CursorMoved { position, .. } => {
-                    let physical_position = DVec2::new(position.x, position.y);
-
-                    let last_position = win.physical_cursor_position();
-                    let delta = last_position.map(|last_pos| {
-                        (physical_position.as_vec2() - last_pos) / win.resolution.scale_factor()
-                    });
-
-                    win.set_physical_cursor_position(Some(physical_position));
-                    let position =
-                        (physical_position / win.resolution.scale_factor() as f64).as_vec2();
-                    app.send_event(CursorMoved {
-                        window,
-                        position,
-                        delta,
-                    });
-                }
-                WindowEvent::CursorEntered { .. } => {
-                    app.send_event(CursorEntered { window });
-                }
-                WindowEvent::CursorLeft { .. } => {
-                    win.set_physical_cursor_position(None);
-                    app.send_event(CursorLeft { window });
-                }
-                WindowEvent::MouseInput { state, button, .. } => {
-                    app.send_event(MouseButtonInput {
-                        button: converters::convert_mouse_button(button),
-                        state: converters::convert_element_state(state),
-                        window,
-                    });
-                }
-                WindowEvent::TouchpadMagnify { delta, .. } => {
-                    app.send_event(TouchpadMagnify(delta as f32));
-                }
-                WindowEvent::TouchpadRotate { delta, .. } => {
-                    app.send_event(TouchpadRotate(delta));
-                }
-                WindowEvent::MouseWheel { delta, .. } => match delta {
-                    event::MouseScrollDelta::LineDelta(x, y) => {
-                        app.send_event(MouseWheel {
-                            unit: MouseScrollUnit::Line,
-                            x,
-                            y,
-                            window,
-                        });
-                    }
-                    event::MouseScrollDelta::PixelDelta(p) => {
-                        app.send_event(MouseWheel {
-                            unit: MouseScrollUnit::Pixel,
-                            x: p.x as f32,
-                            y: p.y as f32,
-                            window,
-                        });
-                    }
-                },
-                WindowEvent::Touch(touch) => {
-                    let location = touch
-                        .location
-                        .to_logical(win.resolution.scale_factor() as f64);
-                    app.send_event(converters::convert_touch_input(touch, location, window));
-                }
-                WindowEvent::ScaleFactorChanged {
-                    scale_factor,
-                    mut inner_size_writer,
-                } => {
-                    let prior_factor = win.resolution.scale_factor();
-                    win.resolution.set_scale_factor(scale_factor as f32);
-                    // Note: this may be different from new_scale_factor if
-                    // `scale_factor_override` is set to Some(thing)
-                    let new_factor = win.resolution.scale_factor();
-
-                    let mut new_inner_size =
-                        PhysicalSize::new(win.physical_width(), win.physical_height());
-                    let scale_factor_override = win.resolution.scale_factor_override();
-                    if let Some(forced_factor) = scale_factor_override {
-                        // This window is overriding the OS-suggested DPI, so its physical size
-                        // should be set based on the overriding value. Its logical size already
-                        // incorporates any resize constraints.
-                        let maybe_new_inner_size = LogicalSize::new(win.width(), win.height())
-                            .to_physical::<u32>(forced_factor as f64);
-                        if let Err(err) = inner_size_writer.request_inner_size(new_inner_size) {
-                            warn!("Winit Failed to resize the window: {err}");
-                        } else {
-                            new_inner_size = maybe_new_inner_size;
+            let redraw_requested = matches!(event, WindowEvent::RedrawRequested);
+            let forwarded = handle_window_event(
+                app,
+                runner_state,
+                event_writer_system_state,
+                window,
+                event,
+                log_input_events,
+                |access_kit_adapters, winit_windows, event| {
+                    if let Some(adapter) = access_kit_adapters.get(&window) {
+                        if let Some(winit_window) = winit_windows.get_window(window) {
+                            adapter.process_event(winit_window, event);
                         }
                     }
-                    let new_logical_width = new_inner_size.width as f32 / new_factor;
-                    let new_logical_height = new_inner_size.height as f32 / new_factor;
-
-                    let width_equal = relative_eq!(win.width(), new_logical_width);
-                    let height_equal = relative_eq!(win.height(), new_logical_height);
-                    win.resolution
-                        .set_physical_resolution(new_inner_size.width, new_inner_size.height);
-
-                    app.send_event(WindowBackendScaleFactorChanged {
-                        window,
-                        scale_factor,
-                    });
-                    if scale_factor_override.is_none() && !relative_eq!(new_factor, prior_factor) {
-                        app.send_event(WindowScaleFactorChanged {
-                            window,
-                            scale_factor,
-                        });
-                    }
-
-                    if !width_equal || !height_equal {
-                        app.send_event(WindowResized {
-                            window,
-                            width: new_logical_width,
-                            height: new_logical_height,
-                        });
-                    }
-                }
-                WindowEvent::Focused(focused) => {
-                    win.focused = focused;
-                    app.send_event(WindowFocused { window, focused });
-                }
-                WindowEvent::Occluded(occluded) => {
-                    app.send_event(WindowOccluded { window, occluded });
-                }
-                WindowEvent::DroppedFile(path_buf) => {
-                    app.send_event(FileDragAndDrop::DroppedFile { window, path_buf });
-                }
-                WindowEvent::HoveredFile(path_buf) => {
-                    app.send_event(FileDragAndDrop::HoveredFile { window, path_buf });
-                }
-                WindowEvent::HoveredFileCancelled => {
-                    app.send_event(FileDragAndDrop::HoveredFileCanceled { window });
-                }
-                WindowEvent::Moved(position) => {
-                    let position = ivec2(position.x, position.y);
-                    win.position.set(position);
-                    app.send_event(WindowMoved { window, position });
-                }
-                WindowEvent::Ime(event) => match event {
-                    event::Ime::Preedit(value, cursor) => {
-                        app.send_event(Ime::Preedit {
-                            window,
-                            value,
-                            cursor,
-                        });
-                    }
-                    event::Ime::Commit(value) => {
-                        app.send_event(Ime::Commit { window, value });
-                    }
-                    event::Ime::Enabled => {
-                        app.send_event(Ime::Enabled { window });
-                    }
-                    event::Ime::Disabled => {
-                        app.send_event(Ime::Disabled { window });
-                    }
                 },
-                WindowEvent::ThemeChanged(theme) => {
-                    app.send_event(WindowThemeChanged {
-                        window,
-                        theme: convert_winit_theme(theme),
-                    });
-                }
-                WindowEvent::Destroyed => {
-                    app.send_event(WindowDestroyed { window });
-                }
-                WindowEvent::RedrawRequested => {
-                    run_app_update_if_should(
-                        runner_state,
-                        app,
-                        focused_windows_state,
-                        event_loop,
-                        create_window,
-                        app_exit_event_reader,
-                        redraw_event_reader,
-                    );
-                }
-                _ => {}
+            );
+            if !forwarded {
+                return;
+            }
+            if redraw_requested {
+                run_app_update_if_should(
+                    runner_state,
+                    app,
+                    focused_windows_state,
+                    event_loop,
+                    create_window,
+                    app_exit_event_reader,
+                    redraw_event_reader,
+                );
             }
 
             let mut windows = app.world.query::<(&mut Window, &mut CachedWindow)>();
@@ -628,11 +730,15 @@ fn handle_winit_event(
             runner_state.device_event_received = true;
             if let DeviceEvent::MouseMotion { delta: (x, y) } = event {
                 let delta = Vec2::new(x as f32, y as f32);
-                app.send_event(MouseMotion { delta });
+                app.send_event(MouseMotion { delta }, log_input_events);
             }
         }
+        Event::UserEvent(()) => {
+            // Sent through `EventLoopProxyResource` to wake the app up.
+            runner_state.redraw_requested = true;
+        }
         Event::Suspended => {
-            app.send_event(ApplicationLifetime::Suspended);
+            app.send_event(ApplicationLifetime::Suspended, log_input_events);
             // Mark the state as `WillSuspend`. This will let the schedule run one last time
             // before actually suspending to let the application react
             runner_state.active = ActiveState::WillSuspend;
@@ -641,14 +747,17 @@ fn handle_winit_event(
             #[cfg(any(target_os = "android", target_os = "ios", target_os = "macos"))]
             {
                 if runner_state.active == ActiveState::NotYetStarted {
-                    create_windows(event_loop, create_window.get_mut(&mut app.world));
-                    create_window.apply(&mut app.world);
+                    create_pending_windows(&mut app.world, create_window, |params| {
+                        create_windows(event_loop, params);
+                    });
                 }
             }
 
             match runner_state.active {
-                ActiveState::NotYetStarted => app.send_event(ApplicationLifetime::Started),
-                _ => app.send_event(ApplicationLifetime::Resumed),
+                ActiveState::NotYetStarted => {
+                    app.send_event(ApplicationLifetime::Started, log_input_events);
+                }
+                _ => app.send_event(ApplicationLifetime::Resumed, log_input_events),
             }
             runner_state.active = ActiveState::Active;
             runner_state.redraw_requested = true;
@@ -671,21 +780,24 @@ fn handle_winit_event(
                         accessibility_requested,
                     ) = create_window.get_mut(&mut app.world);
 
-                    let winit_window = winit_windows.create_window(
+                    match winit_windows.create_window(
                         event_loop,
                         entity,
                         &window,
                         &mut adapters,
                         &mut handlers,
                         &accessibility_requested,
-                    );
-
-                    let wrapper = RawHandleWrapper {
-                        window_handle: winit_window.window_handle().unwrap().as_raw(),
-                        display_handle: winit_window.display_handle().unwrap().as_raw(),
-                    };
-
-                    app.world.entity_mut(entity).insert(wrapper);
+                    ) {
+                        Ok(winit_window) => {
+                            let wrapper = RawHandleWrapper {
+                                window_handle: winit_window.window_handle().unwrap().as_raw(),
+                                display_handle: winit_window.display_handle().unwrap().as_raw(),
+                            };
+
+                            app.world.entity_mut(entity).insert(wrapper);
+                        }
+                        Err(err) => error!("Failed to recreate window {entity:?}: {err}"),
+                    }
                 }
                 event_loop.set_control_flow(ControlFlow::Wait);
             }
@@ -693,11 +805,301 @@ fn handle_winit_event(
         _ => (),
     }
 }
+/// Sends the events for a [`WindowEvent`] of `window` to the app, logging them if
+/// `log_input_events` is set.
+///
+/// `process_accessibility` is called first to allow AccessKit to respond to the event before it
+/// reaches the engine, even if the [`RawWindowEventFilter`] keeps it from reaching the app.
+///
+/// Returns `false` if the event wasn't forwarded, because it was filtered out or `window` has no
+/// [`Window`] component. [`WindowEvent::RedrawRequested`] has no events of its own, so running an
+/// update for it is left to the caller.
+fn handle_window_event(
+    app: &mut App,
+    runner_state: &mut WinitAppRunnerState,
+    event_writer_system_state: &mut SystemState<WindowEventParams>,
+    window: Entity,
+    event: WindowEvent,
+    log_input_events: bool,
+    process_accessibility: impl FnOnce(&AccessKitAdapters, &WinitWindows, &WindowEvent),
+) -> bool {
+    let settings = app.world.resource::<WinitSettings>();
+    let resize_coalescing_threshold = settings.resize_coalescing_threshold;
+    let background_when_occluded = settings.background_when_occluded;
+    let forward_event = app
+        .world
+        .get_resource_mut::<RawWindowEventFilter>()
+        .map_or(true, |mut filter| filter.forward(&event));
+    let (mut window_resized, winit_windows, mut windows, access_kit_adapters) =
+        event_writer_system_state.get_mut(&mut app.world);
+
+    let Ok((mut win, _)) = windows.get_mut(window) else {
+        warn!("Window {window:?} is missing `Window` component, skipping event {event:?}");
+        return false;
+    };
+
+    process_accessibility(&access_kit_adapters, &winit_windows, &event);
+
+    runner_state.window_event_received = true;
+
+    if !forward_event {
+        return false;
+    }
+
+    match event {
+        WindowEvent::Resized(size) => {
+            let previous_size = Vec2::new(win.width(), win.height());
+            let resized = react_to_resize(&mut win, size, window);
+            let resized = match resize_coalescing_threshold {
+                Some(threshold) => {
+                    runner_state
+                        .pending_resizes
+                        .push(resized, previous_size, threshold)
+                }
+                None => Some(resized),
+            };
+            if let Some(resized) = resized {
+                if log_input_events {
+                    trace!("Forwarding {resized:?}");
+                }
+                window_resized.send(resized);
+            }
+        }
+        WindowEvent::CloseRequested => {
+            app.send_event(WindowCloseRequested { window }, log_input_events);
+        }
+        WindowEvent::KeyboardInput { ref event, .. } => {
+            if event.state.is_pressed() {
+                if let Some(char) = &event.text {
+                    let char = char.clone();
+                    app.send_event(ReceivedCharacter { window, char }, log_input_events);
+                }
+            }
+            app.send_event(
+                converters::convert_keyboard_input(event, window),
+                log_input_events,
+            );
+        }
+        WindowEvent::CursorMoved { position, .. } => {
+            let physical_position = DVec2::new(position.x, position.y);
+
+            let last_position = win.physical_cursor_position();
+            let delta = last_position.map(|last_pos| {
+                (physical_position.as_vec2() - last_pos) / win.resolution.scale_factor()
+            });
+
+            win.set_physical_cursor_position(Some(physical_position));
+            let position = (physical_position / win.resolution.scale_factor() as f64).as_vec2();
+            app.send_event(
+                CursorMoved {
+                    window,
+                    position,
+                    delta,
+                },
+                log_input_events,
+            );
+        }
+        WindowEvent::CursorEntered { .. } => {
+            if let Some(mut cursor_window) = app.world.get_resource_mut::<CursorWindow>() {
+                cursor_window.enter(window);
+            }
+            app.send_event(CursorEntered { window }, log_input_events);
+        }
+        WindowEvent::CursorLeft { .. } => {
+            win.set_physical_cursor_position(None);
+            if let Some(mut cursor_window) = app.world.get_resource_mut::<CursorWindow>() {
+                cursor_window.leave(window);
+            }
+            app.send_event(CursorLeft { window }, log_input_events);
+        }
+        WindowEvent::MouseInput { state, button, .. } => {
+            app.send_event(
+                MouseButtonInput {
+                    button: converters::convert_mouse_button(button),
+                    state: converters::convert_element_state(state),
+                    window,
+                },
+                log_input_events,
+            );
+        }
+        WindowEvent::TouchpadMagnify { delta, .. } => {
+            app.send_event(TouchpadMagnify(delta as f32), log_input_events);
+        }
+        WindowEvent::TouchpadRotate { delta, .. } => {
+            app.send_event(TouchpadRotate(delta), log_input_events);
+        }
+        WindowEvent::MouseWheel { delta, .. } => match delta {
+            event::MouseScrollDelta::LineDelta(x, y) => {
+                app.send_event(
+                    MouseWheel {
+                        unit: MouseScrollUnit::Line,
+                        x,
+                        y,
+                        window,
+                    },
+                    log_input_events,
+                );
+            }
+            event::MouseScrollDelta::PixelDelta(p) => {
+                app.send_event(
+                    MouseWheel {
+                        unit: MouseScrollUnit::Pixel,
+                        x: p.x as f32,
+                        y: p.y as f32,
+                        window,
+                    },
+                    log_input_events,
+                );
+            }
+        },
+        WindowEvent::Touch(touch) => {
+            let location = touch
+                .location
+                .to_logical(win.resolution.scale_factor() as f64);
+            app.send_event(
+                converters::convert_touch_input(touch, location, window),
+                log_input_events,
+            );
+        }
+        WindowEvent::ScaleFactorChanged {
+            scale_factor,
+            mut inner_size_writer,
+        } => {
+            let prior_factor = win.resolution.scale_factor();
+            win.resolution.set_scale_factor(scale_factor as f32);
+            // Note: this may be different from new_scale_factor if
+            // `scale_factor_override` is set to Some(thing)
+            let new_factor = win.resolution.scale_factor();
+
+            let mut new_inner_size = PhysicalSize::new(win.physical_width(), win.physical_height());
+            let scale_factor_override = win.resolution.scale_factor_override();
+            if let Some(forced_factor) = scale_factor_override {
+                // This window is overriding the OS-suggested DPI, so its physical size
+                // should be set based on the overriding value. Its logical size already
+                // incorporates any resize constraints.
+                let maybe_new_inner_size = LogicalSize::new(win.width(), win.height())
+                    .to_physical::<u32>(forced_factor as f64);
+                if let Err(err) = inner_size_writer.request_inner_size(new_inner_size) {
+                    warn!("Winit Failed to resize the window: {err}");
+                } else {
+                    new_inner_size = maybe_new_inner_size;
+                }
+            }
+            let resized = scale_factor_resize(&win, window, new_inner_size);
+            win.resolution
+                .set_physical_resolution(new_inner_size.width, new_inner_size.height);
+
+            app.send_event(
+                WindowBackendScaleFactorChanged {
+                    window,
+                    scale_factor,
+                },
+                log_input_events,
+            );
+            if scale_factor_override.is_none() && !relative_eq!(new_factor, prior_factor) {
+                app.send_event(
+                    WindowScaleFactorChanged {
+                        window,
+                        scale_factor,
+                    },
+                    log_input_events,
+                );
+            }
+
+            if let Some(resized) = resized {
+                app.send_event(resized, log_input_events);
+            }
+        }
+        WindowEvent::Focused(focused) => {
+            win.focused = focused;
+            // Not every platform clears the composition text when the window loses focus,
+            // which would leave it behind once the window is focused again.
+            if !focused {
+                if let Some(clear) = runner_state.ime_preedits.clear(window) {
+                    app.send_event(clear, log_input_events);
+                }
+            }
+            app.send_event(WindowFocused { window, focused }, log_input_events);
+        }
+        WindowEvent::Occluded(occluded) => {
+            let backgrounded = runner_state.occlusion.set_occluded(
+                window,
+                occluded,
+                winit_windows.entity_to_winit.keys().copied(),
+            );
+            app.send_event(WindowOccluded { window, occluded }, log_input_events);
+            match backgrounded {
+                Some(true) if background_when_occluded => {
+                    app.send_event(AppBackgrounded, log_input_events);
+                }
+                Some(false) if background_when_occluded => {
+                    app.send_event(AppForegrounded, log_input_events);
+                }
+                _ => {}
+            }
+        }
+        WindowEvent::DroppedFile(path_buf) => {
+            app.send_event(
+                FileDragAndDrop::DroppedFile { window, path_buf },
+                log_input_events,
+            );
+        }
+        WindowEvent::HoveredFile(path_buf) => {
+            app.send_event(
+                FileDragAndDrop::HoveredFile { window, path_buf },
+                log_input_events,
+            );
+        }
+        WindowEvent::HoveredFileCancelled => {
+            app.send_event(
+                FileDragAndDrop::HoveredFileCanceled { window },
+                log_input_events,
+            );
+        }
+        WindowEvent::Moved(position) => {
+            let position = ivec2(position.x, position.y);
+            win.position.set(position);
+            app.send_event(WindowMoved { window, position }, log_input_events);
+        }
+        WindowEvent::Ime(event) => {
+            let ime = match event {
+                event::Ime::Preedit(value, cursor) => Ime::Preedit {
+                    window,
+                    value,
+                    cursor,
+                },
+                event::Ime::Commit(value) => Ime::Commit { window, value },
+                event::Ime::Enabled => Ime::Enabled { window },
+                event::Ime::Disabled => Ime::Disabled { window },
+            };
+            runner_state.ime_preedits.track(&ime);
+            app.send_event(ime, log_input_events);
+        }
+        WindowEvent::ThemeChanged(theme) => {
+            app.send_event(
+                WindowThemeChanged {
+                    window,
+                    theme: convert_winit_theme(theme),
+                },
+                log_input_events,
+            );
+        }
+        WindowEvent::Destroyed => {
+            if let Some(mut cursor_window) = app.world.get_resource_mut::<CursorWindow>() {
+                cursor_window.leave(window);
+            }
+            app.send_event(WindowDestroyed { window }, log_input_events);
+        }
+        _ => {}
+    }
+
+    true
+}
 
 fn run_app_update_if_should(
     runner_state: &mut WinitAppRunnerState,
     app: &mut App,
-    focused_windows_state: &mut SystemState<(Res<WinitSettings>, Query<&Window>)>,
+    focused_windows_state: &mut SystemState<UpdateModeParams>,
     event_loop: &EventLoopWindowTarget<()>,
     create_window: &mut SystemState<CreateWindowParams<Added<Window>>>,
     app_exit_event_reader: &mut ManualEventReader<AppExit>,
@@ -722,37 +1124,59 @@ fn run_app_update_if_should(
     }
 
     if app.plugins_state() == PluginsState::Cleaned {
-        runner_state.last_update = Instant::now();
+        let log_input_events = app.world.resource::<WinitSettings>().log_input_events;
+        for resized in runner_state.pending_resizes.drain() {
+            app.send_event(resized, log_input_events);
+        }
+
+        let update_start = Instant::now();
+        let interval = update_start - runner_state.last_update;
+        runner_state.last_update = update_start;
 
         app.update();
 
+        if let Some(mut frame_pacing) = app.world.get_resource_mut::<FramePacing>() {
+            frame_pacing.record(interval, update_start.elapsed());
+        }
+
         // decide when to run the next update
         let (config, windows) = focused_windows_state.get(&app.world);
-        let focused = windows.iter().any(|window| window.focused);
-        match config.update_mode(focused) {
-            UpdateMode::Continuous => {
+        let update_mode = config.effective_update_mode(windows.iter());
+        let refresh_interval = if config.match_monitor_refresh_rate {
+            monitor_refresh_interval(app.world.non_send_resource::<WinitWindows>())
+        } else {
+            None
+        };
+        match next_control_flow(
+            &app.world,
+            update_mode,
+            refresh_interval,
+            runner_state.last_update,
+        ) {
+            ControlFlow::Poll => {
                 runner_state.redraw_requested = true;
             }
-            UpdateMode::Reactive { wait } | UpdateMode::ReactiveLowPower { wait } => {
-                // TODO(bug): this is unexpected behavior.
-                // When Reactive, user expects bevy to actually wait that amount of time,
-                // and not potentially infinitely depending on plateform specifics (which this does)
-                // Need to verify the plateform specifics (whether this can occur in
-                // rare-but-possible cases) and replace this with a panic or a log warn!
-                if let Some(next) = runner_state.last_update.checked_add(*wait) {
-                    runner_state.scheduled_update = Some(next);
-                    event_loop.set_control_flow(ControlFlow::WaitUntil(next));
-                } else {
-                    runner_state.scheduled_update = None;
-                    event_loop.set_control_flow(ControlFlow::Wait);
-                }
+            ControlFlow::WaitUntil(next) => {
+                runner_state.scheduled_update = Some(next);
+                event_loop.set_control_flow(ControlFlow::WaitUntil(next));
+            }
+            ControlFlow::Wait => {
+                runner_state.scheduled_update = None;
+                event_loop.set_control_flow(ControlFlow::Wait);
             }
         }
 
-        if let Some(app_redraw_events) = app.world.get_resource::<Events<RequestRedraw>>() {
-            if redraw_event_reader.read(app_redraw_events).last().is_some() {
-                runner_state.redraw_requested = true;
-            }
+        let redraw_requests = app
+            .world
+            .get_resource::<Events<RequestRedraw>>()
+            .map_or(0, |app_redraw_events| {
+                redraw_event_reader.read(app_redraw_events).count()
+            });
+        if redraw_requests > 0 {
+            runner_state.redraw_requested = true;
+        }
+        if let Some(mut redraw_stats) = app.world.get_resource_mut::<RedrawRequestStats>() {
+            redraw_stats.record(redraw_requests);
         }
 
         if let Some(app_exit_events) = app.world.get_resource::<Events<AppExit>>() {
@@ -764,22 +1188,571 @@ fn run_app_update_if_should(
 
     // create any new windows
     // (even if app did not update, some may have been created by plugin setup)
-    create_windows(event_loop, create_window.get_mut(&mut app.world));
-    create_window.apply(&mut app.world);
+    create_pending_windows(&mut app.world, create_window, |params| {
+        create_windows(event_loop, params);
+    });
+}
+
+/// Runs `create` with the parameters of `create_window`, unless [`DeferWindowCreation`] exists.
+///
+/// This is checked before running `create` rather than inside it, so that the `Added<Window>`
+/// filter still matches the windows spawned while creation was deferred.
+fn create_pending_windows<F: QueryFilter + 'static>(
+    world: &mut World,
+    create_window: &mut SystemState<CreateWindowParams<F>>,
+    create: impl FnOnce(SystemParamItem<CreateWindowParams<F>>),
+) {
+    if world.contains_resource::<DeferWindowCreation>() {
+        return;
+    }
+    create(create_window.get_mut(world));
+    create_window.apply(world);
+}
+
+/// Decides when the update after the one started at `last_update` runs, consulting the
+/// [`ControlFlowPolicy`] if there is one.
+///
+/// `refresh_interval` replaces the `wait` of [`UpdateMode::Reactive`], see
+/// [`WinitSettings::match_monitor_refresh_rate`].
+fn next_control_flow(
+    world: &World,
+    update_mode: UpdateMode,
+    refresh_interval: Option<Duration>,
+    last_update: Instant,
+) -> ControlFlow {
+    let control_flow = match update_mode {
+        UpdateMode::Continuous => ControlFlow::Poll,
+        UpdateMode::Reactive { wait } | UpdateMode::ReactiveLowPower { wait } => {
+            // Waking up at the refresh rate would defeat the purpose of the low power mode.
+            let wait = match (update_mode, refresh_interval) {
+                (UpdateMode::Reactive { .. }, Some(refresh_interval)) => refresh_interval,
+                _ => wait,
+            };
+            // TODO(bug): this is unexpected behavior.
+            // When Reactive, user expects bevy to actually wait that amount of time,
+            // and not potentially infinitely depending on plateform specifics (which this does)
+            // Need to verify the plateform specifics (whether this can occur in
+            // rare-but-possible cases) and replace this with a panic or a log warn!
+            match last_update.checked_add(wait) {
+                Some(next) => ControlFlow::WaitUntil(next),
+                None => ControlFlow::Wait,
+            }
+        }
+    };
+
+    match world.get_resource::<ControlFlowPolicy>() {
+        Some(policy) => policy.decide(&ControlFlowContext {
+            world,
+            update_mode,
+            last_update,
+            control_flow,
+        }),
+        None => control_flow,
+    }
+}
+
+/// Returns the refresh interval of the monitor the focused window is on, or of the monitor of any
+/// window if none of them has focus.
+fn monitor_refresh_interval(winit_windows: &WinitWindows) -> Option<Duration> {
+    let window = winit_windows
+        .windows
+        .values()
+        .find(|window| window.has_focus())
+        .or_else(|| winit_windows.windows.values().next())?;
+    let refresh_rate = window.current_monitor()?.refresh_rate_millihertz()?;
+    winit_config::refresh_interval(refresh_rate)
+}
+
+/// Returns the [`WindowResized`] event to send after the scale factor of `win` changed, if its
+/// logical size changed.
+///
+/// `win` must already use its new scale factor, but not yet its new physical size. This is
+/// also the case if the physical size stays the same, since the logical size then changes with
+/// the scale factor.
+fn scale_factor_resize(
+    win: &Window,
+    window: Entity,
+    new_inner_size: PhysicalSize<u32>,
+) -> Option<WindowResized> {
+    let new_factor = win.resolution.scale_factor();
+    let new_logical_width = new_inner_size.width as f32 / new_factor;
+    let new_logical_height = new_inner_size.height as f32 / new_factor;
+
+    let width_equal = relative_eq!(win.width(), new_logical_width);
+    let height_equal = relative_eq!(win.height(), new_logical_height);
+
+    (!width_equal || !height_equal).then_some(WindowResized {
+        window,
+        width: new_logical_width,
+        height: new_logical_height,
+    })
 }
 
 fn react_to_resize(
     win: &mut Mut<'_, Window>,
     size: winit::dpi::PhysicalSize<u32>,
-    window_resized: &mut EventWriter<WindowResized>,
     window: Entity,
-) {
+) -> WindowResized {
     win.resolution
         .set_physical_resolution(size.width, size.height);
 
-    window_resized.send(WindowResized {
+    WindowResized {
         window,
         width: win.width(),
         height: win.height(),
-    });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        next_control_flow, scale_factor_resize, AppSendEvent, ControlFlowPolicy,
+        DeferWindowCreation, ImePreedits, Occlusion, PendingResizes, RawWindowEventFilter,
+        UpdateMode, WinitAppRunnerState, WinitSettings,
+    };
+    use bevy_app::App;
+    use bevy_ecs::{prelude::*, system::SystemState};
+    use bevy_math::Vec2;
+    use bevy_utils::{Duration, Instant};
+    use bevy_window::{Ime, Window, WindowFocused, WindowResized};
+    use winit::{
+        dpi::PhysicalSize,
+        event::{DeviceId, MouseScrollDelta, TouchPhase, WindowEvent},
+        event_loop::ControlFlow,
+    };
+
+    #[test]
+    fn scale_factor_change_sends_resize_if_logical_size_changes() {
+        let entity = Entity::from_raw(0);
+        // An 800x600 window at a scale factor of 1.0, whose scale factor changes to 2.0 in the
+        // same order as in the event handler: the logical size is kept until the new physical
+        // size is applied.
+        let mut window = Window::default();
+        window.resolution.set_physical_resolution(800, 600);
+        window.resolution.set_scale_factor(2.0);
+
+        // The physical size stays the same, so the logical size halves.
+        assert_eq!(
+            scale_factor_resize(&window, entity, PhysicalSize::new(800, 600)),
+            Some(WindowResized {
+                window: entity,
+                width: 400.0,
+                height: 300.0,
+            })
+        );
+
+        // The physical size grows with the scale factor, so the logical size stays the same.
+        assert_eq!(
+            scale_factor_resize(&window, entity, PhysicalSize::new(1600, 1200)),
+            None
+        );
+    }
+
+    #[test]
+    fn startup_forced_updates_are_configurable() {
+        for count in [0, 1, 10] {
+            let mut runner_state = WinitAppRunnerState::new(count);
+            for _ in 0..count {
+                assert!(runner_state.take_startup_forced_update());
+            }
+            // Once they are used up, updates only run in response to events.
+            assert!(!runner_state.take_startup_forced_update());
+        }
+    }
+
+    #[test]
+    fn rapid_resizes_are_coalesced() {
+        let window = Entity::from_raw(0);
+        let mut pending_resizes = PendingResizes::default();
+
+        let mut previous_size = Vec2::new(800.0, 600.0);
+        for i in 1..=10 {
+            let resized = WindowResized {
+                window,
+                width: 800.0 + i as f32,
+                height: 600.0 + 2.0 * i as f32,
+            };
+            assert_eq!(pending_resizes.push(resized, previous_size, 100.0), None);
+            previous_size = Vec2::new(800.0 + i as f32, 600.0 + 2.0 * i as f32);
+        }
+        assert_eq!(
+            pending_resizes.drain().collect::<Vec<_>>(),
+            vec![WindowResized {
+                window,
+                width: 810.0,
+                height: 620.0,
+            }]
+        );
+        assert_eq!(pending_resizes.drain().count(), 0);
+
+        // Large changes, like entering fullscreen, are sent right away.
+        let fullscreen = WindowResized {
+            window,
+            width: 1920.0,
+            height: 1080.0,
+        };
+        assert_eq!(
+            pending_resizes.push(fullscreen.clone(), previous_size, 100.0),
+            Some(fullscreen)
+        );
+        assert_eq!(pending_resizes.drain().count(), 0);
+    }
+
+    #[test]
+    fn preedit_is_cleared_on_focus_loss() {
+        let window = Entity::from_raw(0);
+        let mut ime_preedits = ImePreedits::default();
+
+        ime_preedits.track(&Ime::Enabled { window });
+        ime_preedits.track(&Ime::Preedit {
+            window,
+            value: "かな".to_string(),
+            cursor: Some((0, 6)),
+        });
+        assert_eq!(
+            ime_preedits.clear(window),
+            Some(Ime::Preedit {
+                window,
+                value: String::new(),
+                cursor: None,
+            })
+        );
+        // The composition text has been cleared, so there is nothing left to clear.
+        assert_eq!(ime_preedits.clear(window), None);
+
+        ime_preedits.track(&Ime::Preedit {
+            window,
+            value: "a".to_string(),
+            cursor: None,
+        });
+        ime_preedits.track(&Ime::Commit {
+            window,
+            value: "a".to_string(),
+        });
+        assert_eq!(ime_preedits.clear(window), None);
+    }
+
+    #[test]
+    fn occluding_all_windows_backgrounds_the_app_once() {
+        let (a, b) = (Entity::from_raw(0), Entity::from_raw(1));
+        let mut occlusion = Occlusion::default();
+        let mut events = Vec::new();
+
+        for (window, occluded) in [(a, true), (b, true), (b, true), (a, false), (b, false)] {
+            events.extend(occlusion.set_occluded(window, occluded, [a, b]));
+        }
+
+        assert_eq!(events, vec![true, false]);
+    }
+
+    #[test]
+    fn raw_window_event_filter_blocks_events() {
+        use super::{handle_window_event, CachedWindow, WindowEventParams, WinitWindows};
+        use crate::accessibility::AccessKitAdapters;
+        use bevy_input::mouse::MouseWheel;
+
+        let mut app = App::new();
+        app.insert_resource(WinitSettings::default())
+            .insert_resource(RawWindowEventFilter::new(|event| {
+                !matches!(event, WindowEvent::MouseWheel { .. })
+            }))
+            .init_non_send_resource::<WinitWindows>()
+            .init_non_send_resource::<AccessKitAdapters>()
+            .add_event::<WindowResized>()
+            .add_event::<MouseWheel>()
+            .add_event::<WindowFocused>();
+        let window = app
+            .world
+            .spawn((
+                Window::default(),
+                CachedWindow {
+                    window: Window::default(),
+                },
+            ))
+            .id();
+        let mut runner_state = WinitAppRunnerState::new(0);
+        let mut event_writer_system_state: SystemState<WindowEventParams> =
+            SystemState::new(&mut app.world);
+
+        let mut handle = |app: &mut App, event: WindowEvent| {
+            let mut accessibility_saw_event = false;
+            let forwarded = handle_window_event(
+                app,
+                &mut runner_state,
+                &mut event_writer_system_state,
+                window,
+                event,
+                false,
+                |_, _, _| accessibility_saw_event = true,
+            );
+            (forwarded, accessibility_saw_event)
+        };
+
+        let mouse_wheel = WindowEvent::MouseWheel {
+            // SAFETY: the id is only compared, never passed to a platform API.
+            device_id: unsafe { DeviceId::dummy() },
+            delta: MouseScrollDelta::LineDelta(0.0, 1.0),
+            phase: TouchPhase::Moved,
+        };
+        assert_eq!(handle(&mut app, mouse_wheel), (false, true));
+        assert!(app.world.resource::<Events<MouseWheel>>().is_empty());
+
+        assert_eq!(handle(&mut app, WindowEvent::Focused(true)), (true, true));
+        assert_eq!(app.world.resource::<Events<WindowFocused>>().len(), 1);
+    }
+
+    #[test]
+    fn deferred_windows_are_created_once_the_gate_clears() {
+        use super::{create_pending_windows, CreateWindowParams, WinitWindows};
+        use crate::accessibility::{AccessKitAdapters, WinitActionHandlers};
+        use bevy_a11y::AccessibilityRequested;
+        use bevy_window::{WindowCreated, WindowCreationFailed};
+
+        let mut world = World::new();
+        world.init_resource::<WinitSettings>();
+        world.init_resource::<WinitActionHandlers>();
+        world.init_resource::<AccessibilityRequested>();
+        world.init_resource::<Events<WindowCreated>>();
+        world.init_resource::<Events<WindowCreationFailed>>();
+        world.init_non_send_resource::<WinitWindows>();
+        world.init_non_send_resource::<AccessKitAdapters>();
+        world.insert_resource(DeferWindowCreation);
+        let window = world.spawn(Window::default()).id();
+        let mut create_window =
+            SystemState::<CreateWindowParams<Added<Window>>>::from_world(&mut world);
+
+        let mut created = Vec::new();
+        for _ in 0..3 {
+            create_pending_windows(&mut world, &mut create_window, |_| {
+                panic!("windows were created while creation was deferred");
+            });
+        }
+
+        world.remove_resource::<DeferWindowCreation>();
+        for _ in 0..2 {
+            create_pending_windows(&mut world, &mut create_window, |(_, _, windows, ..)| {
+                created.extend(windows.iter().map(|(entity, ..)| entity));
+            });
+        }
+        assert_eq!(created, vec![window]);
+    }
+
+    #[test]
+    fn logged_input_events_are_still_forwarded() {
+        use std::{
+            io,
+            sync::{Arc, Mutex},
+        };
+
+        /// Collects the formatted log lines.
+        #[derive(Clone, Default)]
+        struct Logs(Arc<Mutex<Vec<u8>>>);
+
+        impl io::Write for Logs {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        assert!(!WinitSettings::game().log_input_events);
+        assert!(!WinitSettings::desktop_app().log_input_events);
+
+        let mut app = App::new();
+        app.add_event::<WindowFocused>();
+        let window = Entity::from_raw(0);
+        let logs = Logs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing_subscriber::filter::LevelFilter::TRACE)
+            .with_ansi(false)
+            .with_writer({
+                let logs = logs.clone();
+                move || logs.clone()
+            })
+            .finish();
+        bevy_utils::tracing::subscriber::with_default(subscriber, || {
+            for log_input_events in [true, false] {
+                AppSendEvent::send_event(
+                    &mut app,
+                    WindowFocused {
+                        window,
+                        focused: log_input_events,
+                    },
+                    log_input_events,
+                );
+            }
+        });
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<_> = logs.lines().collect();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("TRACE"));
+        assert!(lines[0].contains(&format!(
+            "Forwarding {:?}",
+            WindowFocused {
+                window,
+                focused: true
+            }
+        )));
+
+        let mut reader = app
+            .world
+            .resource_mut::<Events<WindowFocused>>()
+            .get_reader();
+        let events = app.world.resource::<Events<WindowFocused>>();
+        assert_eq!(
+            reader.read(events).collect::<Vec<_>>(),
+            [
+                &WindowFocused {
+                    window,
+                    focused: true
+                },
+                &WindowFocused {
+                    window,
+                    focused: false
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn refresh_interval_only_replaces_the_reactive_wait() {
+        let world = World::new();
+        let wait = Duration::from_secs(60);
+        let refresh_interval = Some(Duration::from_micros(16_667));
+        let last_update = Instant::now();
+
+        assert_eq!(
+            next_control_flow(
+                &world,
+                UpdateMode::Reactive { wait },
+                refresh_interval,
+                last_update
+            ),
+            ControlFlow::WaitUntil(last_update + Duration::from_micros(16_667))
+        );
+        assert_eq!(
+            next_control_flow(&world, UpdateMode::Reactive { wait }, None, last_update),
+            ControlFlow::WaitUntil(last_update + wait)
+        );
+        assert_eq!(
+            next_control_flow(
+                &world,
+                UpdateMode::ReactiveLowPower { wait },
+                refresh_interval,
+                last_update
+            ),
+            ControlFlow::WaitUntil(last_update + wait)
+        );
+    }
+
+    #[test]
+    fn control_flow_policy_takes_precedence() {
+        let mut world = World::new();
+        let wait = Duration::from_secs(5);
+        let last_update = Instant::now();
+        let reactive = UpdateMode::Reactive { wait };
+
+        assert_eq!(
+            next_control_flow(&world, reactive, None, last_update),
+            ControlFlow::WaitUntil(last_update + wait)
+        );
+        assert_eq!(
+            next_control_flow(&world, UpdateMode::Continuous, None, last_update),
+            ControlFlow::Poll
+        );
+
+        world.insert_resource(ControlFlowPolicy::new(|context| {
+            assert!(matches!(context.update_mode, UpdateMode::Reactive { .. }));
+            assert!(matches!(context.control_flow, ControlFlow::WaitUntil(_)));
+            ControlFlow::Poll
+        }));
+        assert_eq!(
+            next_control_flow(&world, reactive, None, last_update),
+            ControlFlow::Poll
+        );
+    }
+
+    #[test]
+    #[cfg(all(target_os = "linux", feature = "x11"))]
+    fn proxy_wakes_the_app_from_another_thread() {
+        use super::{handle_winit_event, EventLoopProxyResource, UpdateModeParams, WinitWindows};
+        use crate::accessibility::{AccessKitAdapters, WinitActionHandlers};
+        use bevy_a11y::AccessibilityRequested;
+        use bevy_app::{AppExit, Update};
+        use bevy_ecs::event::ManualEventReader;
+        use bevy_window::{
+            ApplicationLifetime, RequestRedraw, WindowCreated, WindowCreationFailed,
+        };
+        use winit::{
+            event_loop::EventLoopBuilder,
+            platform::{pump_events::EventLoopExtPumpEvents, x11::EventLoopBuilderExtX11},
+        };
+
+        // Without a display to connect to, there is no event loop to wake.
+        let Ok(mut event_loop) = EventLoopBuilder::new().with_any_thread(true).build() else {
+            return;
+        };
+
+        #[derive(Resource, Default)]
+        struct Updates(u32);
+
+        let mut app = App::new();
+        app.insert_resource(WinitSettings::desktop_app())
+            .init_resource::<Updates>()
+            .init_resource::<WinitActionHandlers>()
+            .init_resource::<AccessibilityRequested>()
+            .init_non_send_resource::<WinitWindows>()
+            .init_non_send_resource::<AccessKitAdapters>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowCreated>()
+            .add_event::<WindowCreationFailed>()
+            .add_event::<ApplicationLifetime>()
+            .add_event::<RequestRedraw>()
+            .add_systems(Update, |mut updates: ResMut<Updates>| updates.0 += 1);
+        app.finish();
+        app.cleanup();
+        app.insert_resource(EventLoopProxyResource::new(&event_loop));
+
+        let mut runner_state = WinitAppRunnerState::new(0);
+        let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
+        let mut redraw_event_reader = ManualEventReader::<RequestRedraw>::default();
+        let mut create_window = SystemState::from_world(&mut app.world);
+        let mut event_writer_system_state = SystemState::new(&mut app.world);
+        let mut focused_windows_state: SystemState<UpdateModeParams> =
+            SystemState::new(&mut app.world);
+        let mut pump = |app: &mut App, runner_state: &mut WinitAppRunnerState| {
+            event_loop.pump_events(Some(Duration::ZERO), |event, event_loop| {
+                handle_winit_event(
+                    app,
+                    &mut app_exit_event_reader,
+                    runner_state,
+                    &mut create_window,
+                    &mut event_writer_system_state,
+                    &mut focused_windows_state,
+                    &mut redraw_event_reader,
+                    event,
+                    event_loop,
+                );
+            });
+        };
+
+        // The first events resume the app, which runs an update and then waits for the
+        // `ReactiveLowPower` timeout since no window has focus.
+        pump(&mut app, &mut runner_state);
+        let updates = app.world.resource::<Updates>().0;
+        assert!(updates > 0);
+        pump(&mut app, &mut runner_state);
+        assert_eq!(app.world.resource::<Updates>().0, updates);
+
+        let proxy = app.world.resource::<EventLoopProxyResource>().proxy();
+        std::thread::spawn(move || proxy.send_event(()).unwrap())
+            .join()
+            .unwrap();
+        pump(&mut app, &mut runner_state);
+        assert_eq!(app.world.resource::<Updates>().0, updates + 1);
+    }
 }
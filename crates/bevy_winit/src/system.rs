@@ -1,14 +1,28 @@
 use bevy_ecs::{
     entity::Entity,
-    event::EventWriter,
+    event::{EventReader, EventWriter},
     prelude::{Changed, Component},
     query::QueryFilter,
     removal_detection::RemovedComponents,
-    system::{NonSendMut, Query, SystemParamItem},
+    system::{Commands, NonSend, NonSendMut, Query, Res, SystemParamItem},
 };
+use bevy_math::UVec2;
 use bevy_utils::tracing::{error, info, warn};
 use bevy_window::{
-    RawHandleWrapper, Window, WindowClosed, WindowCreated, WindowMode, WindowResized,
+    MonitorSelection, MoveWindowToMonitor, RawHandleWrapper, Window, WindowClosed, WindowCreated,
+    WindowCreationFailed, WindowMode, WindowResized,
+};
+use std::fmt::Display;
+#[cfg(feature = "serialize")]
+use {
+    bevy_ecs::{
+        entity::{EntityHashMap, EntityHashSet},
+        system::Local,
+    },
+    bevy_math::ivec2,
+    bevy_utils::{Duration, Instant},
+    bevy_window::{PersistentWindowGeometry, WindowCloseRequested, WindowGeometry, WindowMoved},
+    std::{io, path::Path},
 };
 
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
@@ -22,7 +36,8 @@ use crate::{
         self, convert_enabled_buttons, convert_window_level, convert_window_theme,
         convert_winit_theme,
     },
-    get_best_videomode, get_fitting_videomode, CreateWindowParams, WinitWindows,
+    get_best_videomode, get_fitting_videomode, monitor_rect, position_on_monitor, select_monitor,
+    CreateWindowParams, WinitSettings, WinitWindows,
 };
 
 /// Creates new windows on the [`winit`] backend for each entity with a newly-added
@@ -34,16 +49,18 @@ use crate::{
 pub(crate) fn create_windows<F: QueryFilter + 'static>(
     event_loop: &EventLoopWindowTarget<()>,
     (
+        settings,
         mut commands,
         mut created_windows,
         mut window_created_events,
+        mut window_creation_failed_events,
         mut winit_windows,
         mut adapters,
         mut handlers,
         accessibility_requested,
     ): SystemParamItem<CreateWindowParams<F>>,
 ) {
-    for (entity, mut window) in &mut created_windows {
+    for (entity, mut window, persistent_geometry) in &mut created_windows {
         if winit_windows.get_window(entity).is_some() {
             continue;
         }
@@ -54,7 +71,18 @@ pub(crate) fn create_windows<F: QueryFilter + 'static>(
             entity
         );
 
-        let winit_window = winit_windows.create_window(
+        if settings.disable_dpi_scaling {
+            window.resolution.set_scale_factor_override(Some(1.0));
+        }
+
+        #[cfg(feature = "serialize")]
+        if let Some(persistent_geometry) = persistent_geometry {
+            restore_window_geometry(event_loop, &mut window, &persistent_geometry.path);
+        }
+        #[cfg(not(feature = "serialize"))]
+        let _ = persistent_geometry;
+
+        let created = winit_windows.create_window(
             event_loop,
             entity,
             &window,
@@ -62,25 +90,211 @@ pub(crate) fn create_windows<F: QueryFilter + 'static>(
             &mut handlers,
             &accessibility_requested,
         );
+        finish_window_creation(
+            entity,
+            &mut window,
+            created,
+            &mut commands,
+            &mut window_created_events,
+            &mut window_creation_failed_events,
+        );
+    }
+}
+
+/// Completes the creation of the [`Window`] of `entity` once the backend tried to create it.
+///
+/// On success, the window gets its [`RawHandleWrapper`] and [`CachedWindow`] and
+/// [`WindowCreated`] is sent. Otherwise, [`WindowCreationFailed`] is sent and the entity is left
+/// as it is.
+fn finish_window_creation<E: Display>(
+    entity: Entity,
+    window: &mut Window,
+    created: Result<&winit::window::Window, E>,
+    commands: &mut Commands,
+    window_created_events: &mut EventWriter<WindowCreated>,
+    window_creation_failed_events: &mut EventWriter<WindowCreationFailed>,
+) {
+    let winit_window = match created {
+        Ok(winit_window) => winit_window,
+        Err(err) => {
+            error!("Failed to create window {entity:?}: {err}");
+            window_creation_failed_events.send(WindowCreationFailed {
+                window: entity,
+                reason: err.to_string(),
+            });
+            return;
+        }
+    };
+
+    if let Some(theme) = winit_window.theme() {
+        window.window_theme = Some(convert_winit_theme(theme));
+    }
+
+    window
+        .resolution
+        .set_scale_factor(winit_window.scale_factor() as f32);
+    commands
+        .entity(entity)
+        .insert(RawHandleWrapper {
+            window_handle: winit_window.window_handle().unwrap().as_raw(),
+            display_handle: winit_window.display_handle().unwrap().as_raw(),
+        })
+        .insert(CachedWindow {
+            window: window.clone(),
+        });
+
+    window_created_events.send(WindowCreated { window: entity });
+}
+
+/// Applies the [`WindowGeometry`] saved at `path` to `window`, unless nothing has been saved yet
+/// or the saved position is no longer on a connected monitor.
+#[cfg(feature = "serialize")]
+fn restore_window_geometry(
+    event_loop: &EventLoopWindowTarget<()>,
+    window: &mut Window,
+    path: &Path,
+) {
+    let geometry = match WindowGeometry::load(path) {
+        Ok(geometry) => geometry,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return,
+        Err(err) => {
+            warn!(
+                "Failed to load window geometry from {}: {err}",
+                path.display()
+            );
+            return;
+        }
+    };
+
+    let monitors = event_loop
+        .available_monitors()
+        .map(|monitor| monitor_rect(&monitor));
+    if !geometry.is_on_monitor(monitors) {
+        warn!(
+            "Window geometry saved in {} is not on a connected monitor, using the default geometry",
+            path.display()
+        );
+        return;
+    }
+
+    window.position.set(geometry.position);
+    window.resolution.set(geometry.width, geometry.height);
+    if geometry.maximized {
+        window.set_maximized(true);
+    }
+}
+
+/// How long a window has to stay still after being moved or resized before its geometry is saved.
+#[cfg(feature = "serialize")]
+const GEOMETRY_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// Tracks when the geometry of each window with a [`PersistentWindowGeometry`] is due to be saved,
+/// so that dragging or resizing a window saves it once it stops instead of on every frame.
+#[cfg(feature = "serialize")]
+#[derive(Default)]
+pub(crate) struct GeometrySaves {
+    due: EntityHashMap<Instant>,
+    /// The last geometry saved for each window while it wasn't maximized.
+    restored: EntityHashMap<WindowGeometry>,
+}
+
+#[cfg(feature = "serialize")]
+impl GeometrySaves {
+    /// Postpones the save of `window` until [`GEOMETRY_SAVE_DELAY`] after `now`.
+    fn changed(&mut self, window: Entity, now: Instant) {
+        self.due.insert(window, now + GEOMETRY_SAVE_DELAY);
+    }
+
+    /// Returns the windows whose save is due at `now`, along with the `closing` windows, which
+    /// are saved right away.
+    fn take_due(
+        &mut self,
+        now: Instant,
+        closing: impl IntoIterator<Item = Entity>,
+    ) -> EntityHashSet {
+        let mut due: EntityHashSet = closing.into_iter().collect();
+        self.due.retain(|window, deadline| {
+            let is_due = due.contains(window) || *deadline <= now;
+            if is_due {
+                due.insert(*window);
+            }
+            !is_due
+        });
+        due
+    }
+}
 
-        if let Some(theme) = winit_window.theme() {
-            window.window_theme = Some(convert_winit_theme(theme));
+/// Saves the geometry of windows with a [`PersistentWindowGeometry`] once they stop being moved or
+/// resized for [`GEOMETRY_SAVE_DELAY`], and when they are asked to close.
+#[cfg(feature = "serialize")]
+pub(crate) fn save_window_geometry(
+    mut saves: Local<GeometrySaves>,
+    mut moved: EventReader<WindowMoved>,
+    mut resized: EventReader<WindowResized>,
+    mut close_requested: EventReader<WindowCloseRequested>,
+    windows: Query<(&Window, &PersistentWindowGeometry)>,
+    winit_windows: NonSend<WinitWindows>,
+) {
+    let now = Instant::now();
+    for window in moved
+        .read()
+        .map(|event| event.window)
+        .chain(resized.read().map(|event| event.window))
+    {
+        if windows.contains(window) {
+            saves.changed(window, now);
         }
+    }
+    let closing = close_requested.read().map(|event| event.window);
+    let due = saves.take_due(now, closing.filter(|window| windows.contains(*window)));
 
-        window
-            .resolution
-            .set_scale_factor(winit_window.scale_factor() as f32);
-        commands
-            .entity(entity)
-            .insert(RawHandleWrapper {
-                window_handle: winit_window.window_handle().unwrap().as_raw(),
-                display_handle: winit_window.display_handle().unwrap().as_raw(),
+    for entity in due {
+        let Ok((window, persistent_geometry)) = windows.get(entity) else {
+            continue;
+        };
+        let Some(winit_window) = winit_windows.get_window(entity) else {
+            continue;
+        };
+        // Some platforms, like Wayland, don't expose window positions.
+        let Ok(position) = winit_window.outer_position() else {
+            continue;
+        };
+
+        let path = &persistent_geometry.path;
+        let geometry = if winit_window.is_maximized() {
+            // Keep the geometry from before the window was maximized, so that it is restored
+            // when the window is unmaximized after the next launch. Until this window has been
+            // saved unmaximized, that is the geometry in the file.
+            let restored = match saves.restored.get(&entity) {
+                Some(restored) => Some(*restored),
+                None => WindowGeometry::load(path).ok(),
+            };
+            if let Some(restored) = restored {
+                saves.restored.insert(entity, restored);
+            }
+            restored.map(|restored| WindowGeometry {
+                maximized: true,
+                ..restored
             })
-            .insert(CachedWindow {
-                window: window.clone(),
-            });
+        } else {
+            None
+        };
+        let geometry = geometry.unwrap_or_else(|| WindowGeometry {
+            position: ivec2(position.x, position.y),
+            width: window.width(),
+            height: window.height(),
+            maximized: winit_window.is_maximized(),
+        });
+        if !geometry.maximized {
+            saves.restored.insert(entity, geometry);
+        }
 
-        window_created_events.send(WindowCreated { window: entity });
+        if let Err(err) = geometry.save(path) {
+            warn!(
+                "Failed to save window geometry to {}: {err}",
+                path.display()
+            );
+        }
     }
 }
 
@@ -107,6 +321,57 @@ pub struct CachedWindow {
     pub window: Window,
 }
 
+/// Forces a scale factor of `1.0` on every [`Window`] while
+/// [`WinitSettings::disable_dpi_scaling`] is set, so their logical and physical sizes match.
+pub(crate) fn disable_dpi_scaling(settings: Res<WinitSettings>, mut windows: Query<&mut Window>) {
+    if !settings.disable_dpi_scaling {
+        return;
+    }
+
+    for mut window in &mut windows {
+        if window.resolution.scale_factor_override() != Some(1.0) {
+            window.resolution.set_scale_factor_override(Some(1.0));
+        }
+    }
+}
+
+/// Handles [`MoveWindowToMonitor`] by setting the position of the window, which
+/// [`changed_windows`] then applies.
+pub(crate) fn move_windows_to_monitors(
+    mut move_events: EventReader<MoveWindowToMonitor>,
+    mut windows: Query<&mut Window>,
+    winit_windows: NonSend<WinitWindows>,
+) {
+    for event in move_events.read() {
+        let (Ok(mut window), Some(winit_window)) = (
+            windows.get_mut(event.window),
+            winit_windows.get_window(event.window),
+        ) else {
+            continue;
+        };
+
+        let monitor = match event.monitor {
+            MonitorSelection::Current => winit_window.current_monitor(),
+            MonitorSelection::Primary => winit_window.primary_monitor(),
+            MonitorSelection::Index(n) => winit_window.available_monitors().nth(n),
+        };
+        let Some(monitor) = monitor else {
+            warn!(
+                "Couldn't move window {:?} to the monitor selected with {:?}, no such monitor is connected",
+                event.window, event.monitor
+            );
+            continue;
+        };
+
+        let window_size = winit_window.outer_size();
+        window.position.set(position_on_monitor(
+            monitor_rect(&monitor),
+            UVec2::new(window_size.width, window_size.height),
+            event.offset,
+        ));
+    }
+}
+
 /// Propagates changes from [`Window`] entities to the [`winit`] backend.
 ///
 /// # Notes
@@ -130,16 +395,25 @@ pub(crate) fn changed_windows(
         }
 
         if window.mode != cache.window.mode {
+            let select_window_monitor = |monitor_selection| {
+                select_monitor(
+                    &monitor_selection,
+                    winit_window.available_monitors(),
+                    winit_window.primary_monitor(),
+                    winit_window.current_monitor(),
+                )
+            };
             let new_mode = match window.mode {
-                WindowMode::BorderlessFullscreen => {
-                    Some(Some(winit::window::Fullscreen::Borderless(None)))
-                }
-                mode @ (WindowMode::Fullscreen | WindowMode::SizedFullscreen) => {
-                    if let Some(current_monitor) = winit_window.current_monitor() {
+                WindowMode::BorderlessFullscreen(monitor_selection) => Some(Some(
+                    winit::window::Fullscreen::Borderless(select_window_monitor(monitor_selection)),
+                )),
+                mode @ (WindowMode::Fullscreen(monitor_selection)
+                | WindowMode::SizedFullscreen(monitor_selection)) => {
+                    if let Some(monitor) = select_window_monitor(monitor_selection) {
                         let videomode = match mode {
-                            WindowMode::Fullscreen => get_best_videomode(&current_monitor),
-                            WindowMode::SizedFullscreen => get_fitting_videomode(
-                                &current_monitor,
+                            WindowMode::Fullscreen(_) => get_best_videomode(&monitor),
+                            WindowMode::SizedFullscreen(_) => get_fitting_videomode(
+                                &monitor,
                                 window.width() as u32,
                                 window.height() as u32,
                             ),
@@ -148,7 +422,7 @@ pub(crate) fn changed_windows(
 
                         Some(Some(winit::window::Fullscreen::Exclusive(videomode)))
                     } else {
-                        warn!("Could not determine current monitor, ignoring exclusive fullscreen request for window {:?}", window.title);
+                        warn!("Could not determine monitor, ignoring exclusive fullscreen request for window {:?}", window.title);
                         None
                     }
                 }
@@ -167,7 +441,7 @@ pub(crate) fn changed_windows(
                 window.resolution.physical_height(),
             );
             if let Some(size_now) = winit_window.request_inner_size(physical_size) {
-                crate::react_to_resize(&mut window, size_now, &mut window_resized, entity);
+                window_resized.send(crate::react_to_resize(&mut window, size_now, entity));
             }
         }
 
@@ -307,3 +581,112 @@ pub(crate) fn changed_windows(
         cache.window = window.clone();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{disable_dpi_scaling, finish_window_creation, CachedWindow};
+    use crate::WinitSettings;
+    use bevy_ecs::{
+        event::{EventWriter, Events},
+        schedule::Schedule,
+        system::{Commands, Query},
+        world::World,
+    };
+    use bevy_window::{RawHandleWrapper, Window, WindowCreated, WindowCreationFailed};
+
+    #[test]
+    fn disabled_dpi_scaling_matches_logical_and_physical_size() {
+        let mut world = World::new();
+        world.insert_resource(WinitSettings {
+            disable_dpi_scaling: true,
+            ..WinitSettings::default()
+        });
+        let mut window = Window::default();
+        window.resolution.set_scale_factor(2.0);
+        window.resolution.set(800.0, 600.0);
+        let entity = world.spawn(window).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(disable_dpi_scaling);
+        schedule.run(&mut world);
+
+        let window = world.get::<Window>(entity).unwrap();
+        assert_eq!(window.resolution.scale_factor(), 1.0);
+        assert_eq!(window.width(), 800.0);
+        assert_eq!(window.physical_width(), 800);
+        assert_eq!(window.physical_height(), 600);
+    }
+
+    #[test]
+    fn failed_window_creation_sends_an_event() {
+        let mut world = World::new();
+        world.init_resource::<Events<WindowCreated>>();
+        world.init_resource::<Events<WindowCreationFailed>>();
+        let entity = world.spawn(Window::default()).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            move |mut commands: Commands,
+                  mut windows: Query<&mut Window>,
+                  mut created: EventWriter<WindowCreated>,
+                  mut failed: EventWriter<WindowCreationFailed>| {
+                let mut window = windows.get_mut(entity).unwrap();
+                finish_window_creation(
+                    entity,
+                    &mut window,
+                    Err::<&winit::window::Window, _>("surface limit reached"),
+                    &mut commands,
+                    &mut created,
+                    &mut failed,
+                );
+            },
+        );
+        schedule.run(&mut world);
+
+        let failed = world.resource::<Events<WindowCreationFailed>>();
+        assert_eq!(
+            failed.iter_current_update_events().collect::<Vec<_>>(),
+            [&WindowCreationFailed {
+                window: entity,
+                reason: "surface limit reached".to_string(),
+            }]
+        );
+        assert!(world.resource::<Events<WindowCreated>>().is_empty());
+        let window = world.entity(entity);
+        assert!(window.contains::<Window>());
+        assert!(!window.contains::<RawHandleWrapper>());
+        assert!(!window.contains::<CachedWindow>());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn geometry_is_saved_once_the_window_stops_changing() {
+        use super::{GeometrySaves, GEOMETRY_SAVE_DELAY};
+        use bevy_ecs::entity::Entity;
+        use bevy_utils::{Duration, Instant};
+
+        let window = Entity::from_raw(0);
+        let closing = Entity::from_raw(1);
+        let start = Instant::now();
+        let mut saves = GeometrySaves::default();
+
+        saves.changed(window, start);
+        assert!(saves.take_due(start, []).is_empty());
+        // Moving again pushes the save back.
+        let moved_again = start + GEOMETRY_SAVE_DELAY / 2;
+        saves.changed(window, moved_again);
+        assert!(saves.take_due(start + GEOMETRY_SAVE_DELAY, []).is_empty());
+
+        let quiet = moved_again + GEOMETRY_SAVE_DELAY;
+        assert!(saves.take_due(quiet, []).contains(&window));
+        assert!(saves
+            .take_due(quiet + Duration::from_secs(1), [])
+            .is_empty());
+
+        // Closing windows are saved right away, and only once.
+        saves.changed(closing, quiet);
+        let due = saves.take_due(quiet, [closing]);
+        assert!(due.contains(&closing));
+        assert!(saves.take_due(quiet + GEOMETRY_SAVE_DELAY, []).is_empty());
+    }
+}
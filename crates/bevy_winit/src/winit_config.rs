@@ -1,13 +1,65 @@
-use bevy_ecs::system::Resource;
+use bevy_ecs::{component::Component, system::Resource};
 use bevy_utils::Duration;
+use bevy_window::Window;
 
 /// Settings for the [`WinitPlugin`](super::WinitPlugin).
+///
+/// The update modes can be overridden by inserting a [`ControlFlowPolicy`](super::ControlFlowPolicy).
 #[derive(Debug, Resource)]
 pub struct WinitSettings {
     /// Determines how frequently the application can update when it has focus.
     pub focused_mode: UpdateMode,
     /// Determines how frequently the application can update when it's out of focus.
     pub unfocused_mode: UpdateMode,
+    /// If `true`, the `wait` of [`UpdateMode::Reactive`] is replaced by the refresh interval of
+    /// the monitor the focused window is on, so that reactive updates line up with the display's
+    /// refresh rate instead of drifting against it. [`UpdateMode::ReactiveLowPower`] keeps its
+    /// `wait`, so that unfocused apps don't wake up at the refresh rate.
+    ///
+    /// The monitor is queried again after every update, so moving a window to a monitor with a
+    /// different refresh rate is picked up automatically. If the platform doesn't report a refresh
+    /// rate, the configured `wait` is used instead.
+    pub match_monitor_refresh_rate: bool,
+    /// If `true`, every window uses a scale factor of `1.0` regardless of the scale factor
+    /// reported by the OS, so that one logical pixel is always one physical pixel.
+    ///
+    /// This is done by setting [`WindowResolution::scale_factor_override`] on all windows, which
+    /// also makes cursor positions and other window events report physical pixels.
+    /// [`WindowBackendScaleFactorChanged`] events are still sent, but don't affect window sizes.
+    ///
+    /// [`WindowResolution::scale_factor_override`]: bevy_window::WindowResolution::scale_factor_override
+    /// [`WindowBackendScaleFactorChanged`]: bevy_window::WindowBackendScaleFactorChanged
+    pub disable_dpi_scaling: bool,
+    /// The number of updates the runner forces right after the app starts, regardless of the
+    /// [`UpdateMode`], so that plugins get a chance to initialize before the app waits for events.
+    ///
+    /// With `0`, the first update runs once the event loop receives its first event.
+    /// Defaults to `5`.
+    pub startup_forced_updates: u32,
+    /// If `Some`, the [`WindowResized`] events of a window are coalesced so that only the final
+    /// size is sent once per update, instead of one event for every size the window went through.
+    ///
+    /// This avoids redundant work while a window is being resized interactively. A resize that
+    /// changes the width or height by at least this many logical pixels at once, such as toggling
+    /// fullscreen, is still sent right away.
+    ///
+    /// [`WindowResized`]: bevy_window::WindowResized
+    pub resize_coalescing_threshold: Option<f32>,
+    /// If `true`, an [`AppBackgrounded`] event is sent once every window is occluded, and an
+    /// [`AppForegrounded`] event once one of them is visible again.
+    ///
+    /// This lets audio and other background work be paused in one place instead of tracking
+    /// [`WindowOccluded`] events for each window. Occlusion is only reported on some platforms.
+    ///
+    /// [`AppBackgrounded`]: bevy_window::AppBackgrounded
+    /// [`AppForegrounded`]: bevy_window::AppForegrounded
+    /// [`WindowOccluded`]: bevy_window::WindowOccluded
+    pub background_when_occluded: bool,
+    /// If `true`, every event the runner sends to the app in response to a `winit` event is
+    /// logged at the `trace` level, including the window it's for.
+    ///
+    /// This helps diagnose input that doesn't reach the app. Defaults to `false`.
+    pub log_input_events: bool,
 }
 
 impl WinitSettings {
@@ -21,6 +73,13 @@ impl WinitSettings {
             unfocused_mode: UpdateMode::ReactiveLowPower {
                 wait: Duration::from_secs_f64(1.0 / 60.0), // 60Hz
             },
+            match_monitor_refresh_rate: false,
+            disable_dpi_scaling: false,
+            // 3 seems to be enough, 5 is a safe margin
+            startup_forced_updates: 5,
+            resize_coalescing_threshold: None,
+            background_when_occluded: false,
+            log_input_events: false,
         }
     }
 
@@ -36,6 +95,13 @@ impl WinitSettings {
             unfocused_mode: UpdateMode::ReactiveLowPower {
                 wait: Duration::from_secs(60),
             },
+            match_monitor_refresh_rate: false,
+            disable_dpi_scaling: false,
+            // 3 seems to be enough, 5 is a safe margin
+            startup_forced_updates: 5,
+            resize_coalescing_threshold: None,
+            background_when_occluded: false,
+            log_input_events: false,
         }
     }
 
@@ -48,6 +114,55 @@ impl WinitSettings {
             false => &self.unfocused_mode,
         }
     }
+
+    /// Returns the [`UpdateMode`] that satisfies the app and every window.
+    ///
+    /// The app uses the mode of these settings for whether any window has focus, as returned by
+    /// [`update_mode`](Self::update_mode). The [`WindowUpdateMode`] of each window that has one is
+    /// merged into it.
+    pub(crate) fn effective_update_mode<'a>(
+        &self,
+        windows: impl Iterator<Item = (&'a Window, Option<&'a WindowUpdateMode>)>,
+    ) -> UpdateMode {
+        let mut focused = false;
+        let mut overrides = Vec::new();
+        for (window, update_mode) in windows {
+            focused |= window.focused;
+            overrides.extend(update_mode.map(|update_mode| update_mode.0));
+        }
+        overrides
+            .into_iter()
+            .fold(*self.update_mode(focused), UpdateMode::merge)
+    }
+}
+
+/// Requests an [`UpdateMode`] for a single [`Window`] entity, on top of the one of
+/// [`WinitSettings`].
+///
+/// The app updates often enough to satisfy both [`WinitSettings`] and every window with this
+/// component: if any of them is [`Continuous`](UpdateMode::Continuous) the app updates
+/// continuously, and otherwise it waits for the shortest `wait` of all of them.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct WindowUpdateMode(pub UpdateMode);
+
+/// While this resource exists, the [`WinitPlugin`](super::WinitPlugin) doesn't create the OS
+/// windows of [`Window`] entities.
+///
+/// Insert it before adding the plugin to finish heavy initialization before any window appears,
+/// then remove it once the content is ready. Windows spawned in the meantime are created on the
+/// next iteration of the event loop after the resource is removed.
+///
+/// Without a window at startup, the renderer is initialized without a surface to check
+/// compatibility with, which may select an adapter that can't present on some backends.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct DeferWindowCreation;
+
+/// Returns the time between two refreshes of a monitor running at `refresh_rate_millihertz`.
+///
+/// Returns `None` for a refresh rate of zero, which some platforms report for unknown rates.
+pub(crate) fn refresh_interval(refresh_rate_millihertz: u32) -> Option<Duration> {
+    (refresh_rate_millihertz > 0)
+        .then(|| Duration::from_secs_f64(1000.0 / refresh_rate_millihertz as f64))
 }
 
 impl Default for WinitSettings {
@@ -61,7 +176,7 @@ impl Default for WinitSettings {
 /// **Note:** This setting is independent of VSync. VSync is controlled by a window's
 /// [`PresentMode`](bevy_window::PresentMode) setting. If an app can update faster than the refresh
 /// rate, but VSync is enabled, the update rate will be indirectly limited by the renderer.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum UpdateMode {
     /// The [`App`](bevy_app::App) will update over and over, as fast as it possibly can, until an
     /// [`AppExit`](bevy_app::AppExit) event appears.
@@ -97,3 +212,102 @@ pub enum UpdateMode {
         wait: Duration,
     },
 }
+
+impl UpdateMode {
+    /// Combines two modes into one that updates at least as often as both of them.
+    fn merge(self, other: UpdateMode) -> UpdateMode {
+        use UpdateMode::*;
+        match (self, other) {
+            (Continuous, _) | (_, Continuous) => Continuous,
+            (Reactive { wait: a }, Reactive { wait: b } | ReactiveLowPower { wait: b })
+            | (ReactiveLowPower { wait: a }, Reactive { wait: b }) => Reactive { wait: a.min(b) },
+            (ReactiveLowPower { wait: a }, ReactiveLowPower { wait: b }) => {
+                ReactiveLowPower { wait: a.min(b) }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_interval_matches_monitor_rate() {
+        let interval = refresh_interval(60_000).unwrap();
+        assert!((interval.as_secs_f64() - 1.0 / 60.0).abs() < 1e-9);
+
+        let interval = refresh_interval(143_856).unwrap();
+        assert!((interval.as_secs_f64() - 1.0 / 143.856).abs() < 1e-9);
+
+        assert_eq!(refresh_interval(0), None);
+    }
+
+    #[test]
+    fn effective_update_mode_satisfies_every_window() {
+        let settings = WinitSettings::desktop_app();
+        let editor = Window {
+            focused: true,
+            ..Default::default()
+        };
+        let preview = Window {
+            focused: false,
+            ..Default::default()
+        };
+        let editor_mode = WindowUpdateMode(UpdateMode::Reactive {
+            wait: Duration::from_secs_f64(1.0 / 60.0),
+        });
+        let preview_mode = WindowUpdateMode(UpdateMode::ReactiveLowPower {
+            wait: Duration::from_secs_f64(1.0 / 5.0),
+        });
+
+        let mode = settings.effective_update_mode(
+            [
+                (&editor, Some(&editor_mode)),
+                (&preview, Some(&preview_mode)),
+            ]
+            .into_iter(),
+        );
+        assert!(matches!(
+            mode,
+            UpdateMode::Reactive { wait } if wait == Duration::from_secs_f64(1.0 / 60.0)
+        ));
+
+        // Windows without an override only contribute their focus state.
+        let mode = settings
+            .effective_update_mode([(&editor, None), (&preview, Some(&preview_mode))].into_iter());
+        assert!(matches!(
+            mode,
+            UpdateMode::Reactive { wait } if wait == Duration::from_secs_f64(1.0 / 5.0)
+        ));
+
+        // Any continuous window makes the whole app continuous.
+        let continuous = WindowUpdateMode(UpdateMode::Continuous);
+        let mode = settings.effective_update_mode(
+            [(&editor, Some(&editor_mode)), (&preview, Some(&continuous))].into_iter(),
+        );
+        assert!(matches!(mode, UpdateMode::Continuous));
+    }
+
+    #[test]
+    fn effective_update_mode_without_overrides_matches_focus_state() {
+        let settings = WinitSettings::desktop_app();
+        let focused = Window {
+            focused: true,
+            ..Default::default()
+        };
+        let unfocused = Window::default();
+
+        for windows in [
+            vec![],
+            vec![&unfocused],
+            vec![&focused, &unfocused],
+            vec![&unfocused, &unfocused],
+        ] {
+            let any_focused = windows.iter().any(|window| window.focused);
+            let mode =
+                settings.effective_update_mode(windows.into_iter().map(|window| (window, None)));
+            assert_eq!(mode, *settings.update_mode(any_focused));
+        }
+    }
+}
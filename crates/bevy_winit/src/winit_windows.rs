@@ -6,11 +6,15 @@ use bevy_a11y::{
 use bevy_ecs::entity::Entity;
 
 use bevy_ecs::entity::EntityHashMap;
+use bevy_math::{IRect, IVec2, UVec2};
 use bevy_utils::{tracing::warn, HashMap};
-use bevy_window::{CursorGrabMode, Window, WindowMode, WindowPosition, WindowResolution};
+use bevy_window::{
+    CursorGrabMode, MonitorSelection, Window, WindowMode, WindowPosition, WindowResolution,
+};
 
 use winit::{
     dpi::{LogicalSize, PhysicalPosition},
+    error::OsError,
     monitor::MonitorHandle,
 };
 
@@ -37,6 +41,8 @@ pub struct WinitWindows {
 
 impl WinitWindows {
     /// Creates a `winit` window and associates it with our entity.
+    ///
+    /// Returns an error if `winit` fails to build the window.
     pub fn create_window(
         &mut self,
         event_loop: &winit::event_loop::EventLoopWindowTarget<()>,
@@ -45,7 +51,7 @@ impl WinitWindows {
         adapters: &mut AccessKitAdapters,
         handlers: &mut WinitActionHandlers,
         accessibility_requested: &AccessibilityRequested,
-    ) -> &winit::window::Window {
+    ) -> Result<&winit::window::Window, OsError> {
         let mut winit_window_builder = winit::window::WindowBuilder::new();
 
         // Due to a UIA limitation, winit windows need to be invisible for the
@@ -53,15 +59,25 @@ impl WinitWindows {
         winit_window_builder = winit_window_builder.with_visible(false);
 
         winit_window_builder = match window.mode {
-            WindowMode::BorderlessFullscreen => winit_window_builder.with_fullscreen(Some(
-                winit::window::Fullscreen::Borderless(event_loop.primary_monitor()),
-            )),
-            mode @ (WindowMode::Fullscreen | WindowMode::SizedFullscreen) => {
-                if let Some(primary_monitor) = event_loop.primary_monitor() {
+            WindowMode::BorderlessFullscreen(monitor_selection) => winit_window_builder
+                .with_fullscreen(Some(winit::window::Fullscreen::Borderless(select_monitor(
+                    &monitor_selection,
+                    event_loop.available_monitors(),
+                    event_loop.primary_monitor(),
+                    None,
+                )))),
+            mode @ (WindowMode::Fullscreen(monitor_selection)
+            | WindowMode::SizedFullscreen(monitor_selection)) => {
+                if let Some(monitor) = select_monitor(
+                    &monitor_selection,
+                    event_loop.available_monitors(),
+                    event_loop.primary_monitor(),
+                    None,
+                ) {
                     let videomode = match mode {
-                        WindowMode::Fullscreen => get_best_videomode(&primary_monitor),
-                        WindowMode::SizedFullscreen => get_fitting_videomode(
-                            &primary_monitor,
+                        WindowMode::Fullscreen(_) => get_best_videomode(&monitor),
+                        WindowMode::SizedFullscreen(_) => get_fitting_videomode(
+                            &monitor,
                             window.width() as u32,
                             window.height() as u32,
                         ),
@@ -71,7 +87,7 @@ impl WinitWindows {
                     winit_window_builder
                         .with_fullscreen(Some(winit::window::Fullscreen::Exclusive(videomode)))
                 } else {
-                    warn!("Could not determine primary monitor, ignoring exclusive fullscreen request for window {:?}", window.title);
+                    warn!("Could not determine monitor, ignoring exclusive fullscreen request for window {:?}", window.title);
                     winit_window_builder
                 }
             }
@@ -204,7 +220,7 @@ impl WinitWindows {
             winit_window_builder = winit_window_builder.with_append(true);
         }
 
-        let winit_window = winit_window_builder.build(event_loop).unwrap();
+        let winit_window = winit_window_builder.build(event_loop)?;
         let name = window.title.clone();
 
         let mut root_builder = NodeBuilder::new(Role::Window);
@@ -250,10 +266,11 @@ impl WinitWindows {
         self.entity_to_winit.insert(entity, winit_window.id());
         self.winit_to_entity.insert(winit_window.id(), entity);
 
-        self.windows
+        Ok(self
+            .windows
             .entry(winit_window.id())
             .insert(winit_window)
-            .into_mut()
+            .into_mut())
     }
 
     /// Get the winit window that is associated with our entity.
@@ -357,6 +374,62 @@ pub(crate) fn attempt_grab(winit_window: &winit::window::Window, grab_mode: Curs
     }
 }
 
+/// Selects the monitor referenced by `monitor_selection`.
+///
+/// If the selection doesn't match a monitor, this warns and falls back to `current_monitor`, or
+/// to `primary_monitor` if the window doesn't have a monitor yet.
+pub(crate) fn select_monitor<M>(
+    monitor_selection: &MonitorSelection,
+    mut available_monitors: impl Iterator<Item = M>,
+    primary_monitor: Option<M>,
+    current_monitor: Option<M>,
+) -> Option<M> {
+    let warn_invalid = || {
+        warn!("Couldn't get monitor selected with: {monitor_selection:?}, using the current monitor instead");
+    };
+    match monitor_selection {
+        MonitorSelection::Current => current_monitor.or(primary_monitor),
+        MonitorSelection::Primary => primary_monitor.or_else(|| {
+            warn_invalid();
+            current_monitor
+        }),
+        MonitorSelection::Index(n) => available_monitors.nth(*n).or_else(|| {
+            warn_invalid();
+            current_monitor.or(primary_monitor)
+        }),
+    }
+}
+
+/// Returns the bounds of `monitor` in physical pixels.
+pub(crate) fn monitor_rect(monitor: &MonitorHandle) -> IRect {
+    let position = monitor.position();
+    let size = monitor.size();
+    IRect::new(
+        position.x,
+        position.y,
+        position.x + size.width as i32,
+        position.y + size.height as i32,
+    )
+}
+
+/// Computes the physical position of a window of `window_size` moved onto `monitor`, either
+/// centered or at `offset` from the top-left corner of the monitor.
+///
+/// The offset is clamped so that the window stays on the monitor. Windows larger than the
+/// monitor are placed at its top-left corner.
+pub(crate) fn position_on_monitor(
+    monitor: IRect,
+    window_size: UVec2,
+    offset: Option<IVec2>,
+) -> IVec2 {
+    let free_space = (monitor.size() - window_size.as_ivec2()).max(IVec2::ZERO);
+    let offset = match offset {
+        Some(offset) => offset.clamp(IVec2::ZERO, free_space),
+        None => free_space / 2,
+    };
+    monitor.min + offset
+}
+
 /// Compute the physical window position for a given [`WindowPosition`].
 // Ideally we could generify this across window backends, but we only really have winit atm
 // so whatever.
@@ -416,3 +489,60 @@ pub fn winit_window_position(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{position_on_monitor, select_monitor};
+    use bevy_math::{IRect, IVec2, UVec2};
+    use bevy_window::MonitorSelection;
+
+    #[test]
+    fn select_fullscreen_monitor() {
+        // Monitors are mocked by their index.
+        let monitors = || 0..3u32;
+        let select = |selection| select_monitor(&selection, monitors(), Some(0), Some(1));
+
+        assert_eq!(select(MonitorSelection::Current), Some(1));
+        assert_eq!(select(MonitorSelection::Primary), Some(0));
+        assert_eq!(select(MonitorSelection::Index(2)), Some(2));
+        // Invalid selections fall back to the current monitor.
+        assert_eq!(select(MonitorSelection::Index(5)), Some(1));
+        // Windows that don't have a monitor yet fall back to the primary monitor.
+        assert_eq!(
+            select_monitor(&MonitorSelection::Index(5), monitors(), Some(0), None),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn window_is_moved_onto_the_monitor() {
+        // A second monitor to the right of a 1920x1080 one.
+        let monitor = IRect::new(1920, 0, 1920 + 2560, 1440);
+        let window_size = UVec2::new(1280, 720);
+        let on_monitor = |position: IVec2| {
+            monitor.contains(position) && monitor.contains(position + window_size.as_ivec2())
+        };
+
+        let centered = position_on_monitor(monitor, window_size, None);
+        assert_eq!(centered, IVec2::new(1920 + 640, 360));
+        assert!(on_monitor(centered));
+
+        let offset = position_on_monitor(monitor, window_size, Some(IVec2::new(100, 50)));
+        assert_eq!(offset, IVec2::new(2020, 50));
+
+        // Offsets that would push the window off the monitor are clamped.
+        for offset in [IVec2::new(-500, 0), IVec2::new(5000, 5000)] {
+            assert!(on_monitor(position_on_monitor(
+                monitor,
+                window_size,
+                Some(offset)
+            )));
+        }
+
+        // Windows larger than the monitor start at its corner.
+        assert_eq!(
+            position_on_monitor(monitor, UVec2::new(4000, 2000), None),
+            monitor.min
+        );
+    }
+}
@@ -3,7 +3,7 @@
 use bevy::{
     input::touch::TouchPhase,
     prelude::*,
-    window::{ApplicationLifetime, WindowMode},
+    window::{ApplicationLifetime, MonitorSelection, WindowMode},
 };
 
 // the `bevy_main` proc_macro generates the required boilerplate for iOS and Android
@@ -13,7 +13,7 @@ fn main() {
     app.add_plugins(DefaultPlugins.set(WindowPlugin {
         primary_window: Some(Window {
             resizable: false,
-            mode: WindowMode::BorderlessFullscreen,
+            mode: WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
             ..default()
         }),
         ..default()
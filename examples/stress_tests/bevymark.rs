@@ -119,6 +119,7 @@ fn main() {
         .insert_resource(WinitSettings {
             focused_mode: UpdateMode::Continuous,
             unfocused_mode: UpdateMode::Continuous,
+            ..default()
         })
         .insert_resource(args)
         .insert_resource(BevyCounter {
@@ -37,6 +37,7 @@ fn main() {
         .insert_resource(WinitSettings {
             focused_mode: UpdateMode::Continuous,
             unfocused_mode: UpdateMode::Continuous,
+            ..default()
         })
         .add_systems(Startup, setup)
         .add_systems(
@@ -67,6 +67,7 @@ fn main() {
     .insert_resource(WinitSettings {
         focused_mode: UpdateMode::Continuous,
         unfocused_mode: UpdateMode::Continuous,
+        ..default()
     })
     .add_systems(Update, button_system);
 
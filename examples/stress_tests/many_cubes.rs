@@ -90,6 +90,7 @@ fn main() {
         .insert_resource(WinitSettings {
             focused_mode: UpdateMode::Continuous,
             unfocused_mode: UpdateMode::Continuous,
+            ..default()
         })
         .insert_resource(args)
         .add_systems(Startup, setup)
@@ -28,6 +28,7 @@ fn main() {
     .insert_resource(WinitSettings {
         focused_mode: UpdateMode::Continuous,
         unfocused_mode: UpdateMode::Continuous,
+        ..default()
     })
     .insert_resource(Config {
         line_count: 50_000,
@@ -30,6 +30,7 @@ fn main() {
     .insert_resource(WinitSettings {
         focused_mode: UpdateMode::Continuous,
         unfocused_mode: UpdateMode::Continuous,
+        ..default()
     })
     .add_systems(Startup, setup);
 
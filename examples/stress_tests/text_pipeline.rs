@@ -28,6 +28,7 @@ fn main() {
         .insert_resource(WinitSettings {
             focused_mode: UpdateMode::Continuous,
             unfocused_mode: UpdateMode::Continuous,
+            ..default()
         })
         .add_systems(Startup, spawn)
         .add_systems(Update, update_text_bounds)